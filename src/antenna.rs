@@ -0,0 +1,129 @@
+// Antenna pattern approximations
+
+// The parabolic main-lobe fit is only meaningful out to about one beamwidth from boresight,
+// where it reaches 12 dB, past that the real pattern falls into nulls and sidelobes
+const MAX_OFF_AXIS_BEAMWIDTHS: f64 = 1.0;
+
+pub fn pointing_loss_db(off_axis_angle_deg: f64, half_power_beamwidth_deg: f64) -> f64 {
+    // L = 12 * (theta / theta_3dB)^2, 3 dB at half the beamwidth (the -3 dB edge)
+    // Subtract from the transmitter or receiver gain to model mispointing
+    let ratio: f64 = (off_axis_angle_deg.abs() / half_power_beamwidth_deg).min(MAX_OFF_AXIS_BEAMWIDTHS);
+
+    12.0 * ratio * ratio
+}
+
+// the flat ground footprint stretches without bound toward the horizon, below this
+// elevation it is clamped (and the flat ground assumption has long since broken down)
+const MINIMUM_FOOTPRINT_ELEVATION_DEGREES: f64 = 5.0;
+
+pub fn spot_beam_footprint_km2(half_power_beamwidth_deg: f64, altitude_m: f64, elevation_deg: f64) -> f64 {
+    // -3 dB ground footprint as an ellipse, flat ground under a narrow beam
+    // Across the beam the radius is d * tan(theta_3dB / 2) at slant range d, along the
+    // ground toward the satellite it stretches by 1 / sin(elevation), circular at nadir
+    // Elevations are clamped to [5, 90] degrees
+    let elevation_deg: f64 = elevation_deg.clamp(MINIMUM_FOOTPRINT_ELEVATION_DEGREES, 90.0);
+    let slant_range: f64 = crate::fspl::calculate_slant_range(elevation_deg, altitude_m, crate::constants::RADIUS_OF_EARTH);
+    let half_angle: f64 = crate::conversions::angle::degrees_to_radians(half_power_beamwidth_deg / 2.0);
+
+    let minor_radius_km: f64 = slant_range * half_angle.tan() / 1.0e3;
+    let major_radius_km: f64 = minor_radius_km / crate::conversions::angle::degrees_to_radians(elevation_deg).sin();
+
+    std::f64::consts::PI * minor_radius_km * major_radius_km
+}
+
+pub fn effective_aperture_m2(gain_dbi: f64, frequency_hz: f64) -> f64 {
+    // Ae = G * lambda^2 / (4 * pi), the area an antenna of this gain collects power over
+    let gain: f64 = 10.0_f64.powf(gain_dbi / 10.0);
+    let wavelength: f64 = crate::conversions::frequency::frequency_to_wavelength(frequency_hz);
+
+    gain * wavelength * wavelength / (4.0 * std::f64::consts::PI)
+}
+
+pub fn gain_from_aperture_dbi(area_m2: f64, efficiency: f64, frequency_hz: f64) -> f64 {
+    // G = efficiency * 4 * pi * A / lambda^2, so a dish of fixed size gains 6 dB per octave,
+    // the antenna side of FSPL's 20*log10(f): with fixed apertures at both ends the loss falls
+    let wavelength: f64 = crate::conversions::frequency::frequency_to_wavelength(frequency_hz);
+
+    10.0 * (efficiency * 4.0 * std::f64::consts::PI * area_m2 / (wavelength * wavelength)).log10()
+}
+
+#[cfg(test)]
+mod tests {
+
+    #[test]
+    fn boresight() {
+        assert_eq!(0.0, super::pointing_loss_db(0.0, 2.0));
+    }
+
+    #[test]
+    fn half_power_edge() {
+        // theta_3dB is the full beamwidth, so its edge is half of it off axis
+        assert_eq!(3.0, super::pointing_loss_db(1.0, 2.0));
+        assert_eq!(3.0, super::pointing_loss_db(-1.0, 2.0));
+    }
+
+    #[test]
+    fn far_off_axis_is_clamped() {
+        assert_eq!(12.0, super::pointing_loss_db(2.0, 2.0));
+        assert_eq!(12.0, super::pointing_loss_db(45.0, 2.0));
+    }
+
+    #[test]
+    fn nadir_footprint_is_circular() {
+        // 1 degree beam from 550 km, ~4.8 km radius
+        let area: f64 = super::spot_beam_footprint_km2(1.0, 550.0e3, 90.0);
+        let radius_km: f64 = 550.0 * 0.5_f64.to_radians().tan();
+
+        assert!((area - std::f64::consts::PI * radius_km * radius_km).abs() < 1e-6);
+    }
+
+    #[test]
+    fn footprint_clamped_at_low_elevation() {
+        let minimum: f64 = super::spot_beam_footprint_km2(1.0, 550.0e3, 5.0);
+
+        assert!(minimum.is_finite());
+        assert_eq!(minimum, super::spot_beam_footprint_km2(1.0, 550.0e3, 0.0));
+        assert_eq!(minimum, super::spot_beam_footprint_km2(1.0, 550.0e3, -10.0));
+    }
+
+    #[test]
+    fn footprint_grows_off_nadir_and_with_beamwidth() {
+        let nadir: f64 = super::spot_beam_footprint_km2(1.0, 550.0e3, 90.0);
+        let oblique: f64 = super::spot_beam_footprint_km2(1.0, 550.0e3, 45.0);
+        let grazing: f64 = super::spot_beam_footprint_km2(1.0, 550.0e3, 20.0);
+
+        assert!(oblique > nadir);
+        assert!(grazing > oblique);
+
+        assert!(super::spot_beam_footprint_km2(2.0, 550.0e3, 45.0) > oblique);
+    }
+
+    #[test]
+    fn dish_gain() {
+        // 1.2 m Ku-band dish at 65% efficiency, ~41.7 dBi
+        let area: f64 = std::f64::consts::PI * 0.6 * 0.6;
+        let gain: f64 = super::gain_from_aperture_dbi(area, 0.65, 12.0e9);
+
+        assert!(gain > 41.5 && gain < 42.0);
+    }
+
+    #[test]
+    fn aperture_round_trip() {
+        let aperture: f64 = super::effective_aperture_m2(40.0, 20.0e9);
+
+        assert!((super::gain_from_aperture_dbi(aperture, 1.0, 20.0e9) - 40.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn fixed_aperture_gains_six_db_per_octave() {
+        let area: f64 = 1.0;
+
+        let mut previous: f64 = super::gain_from_aperture_dbi(area, 0.6, 4.0e9);
+        for frequency in [8.0e9, 16.0e9, 32.0e9] {
+            let gain: f64 = super::gain_from_aperture_dbi(area, 0.6, frequency);
+
+            assert!((gain - previous - 20.0 * 2.0_f64.log10()).abs() < 1e-9);
+            previous = gain;
+        }
+    }
+}
@@ -0,0 +1,203 @@
+// Clear-sky gaseous absorption (oxygen + water vapor)
+// Simplified slant-path model from ITU-R P.676 Annex 2, valid 1-350 GHz
+
+// oxygen equivalent height (km), P.676 uses ~6 km away from the 60 GHz complex
+const OXYGEN_EQUIVALENT_HEIGHT_KM: f64 = 6.0;
+
+// below a few degrees the cosecant path length blows up (and refraction matters)
+const MINIMUM_ELEVATION_DEGREES: f64 = 5.0;
+
+fn phi(rp: f64, rt: f64, a: f64, b: f64, c: f64, d: f64) -> f64 {
+    rp.powf(a) * rt.powf(b) * (c * (1.0 - rp) + d * (1.0 - rt)).exp()
+}
+
+pub fn oxygen_specific_attenuation_db_per_km(frequency_ghz: f64, surface_pressure_hpa: f64, temperature_k: f64) -> f64 {
+    let f: f64 = frequency_ghz;
+    let rp: f64 = surface_pressure_hpa / 1013.0;
+    let rt: f64 = 288.0 / temperature_k;
+
+    // line strengths around the 60 GHz oxygen complex
+    let gamma_54: f64 = 2.192 * phi(rp, rt, 1.8286, -1.9487, 0.4051, -2.8509);
+    let gamma_58: f64 = 12.59 * phi(rp, rt, 1.0045, 3.5610, 0.1588, 1.2834);
+    let gamma_60: f64 = 15.0 * phi(rp, rt, 0.9003, 4.1335, 0.0427, 1.6088);
+    let gamma_62: f64 = 14.28 * phi(rp, rt, 0.9886, 3.4176, 0.1827, 1.3429);
+    let gamma_64: f64 = 6.819 * phi(rp, rt, 1.4320, 0.6258, 0.3177, -0.5914);
+    let gamma_66: f64 = 1.908 * phi(rp, rt, 2.0717, -4.1404, 0.4910, -4.8718);
+
+    if f <= 54.0 {
+        let xi_1: f64 = phi(rp, rt, 0.0717, -1.8132, 0.0156, -1.6515);
+        let xi_2: f64 = phi(rp, rt, 0.5146, -4.6368, -0.1921, -5.7416);
+        let xi_3: f64 = phi(rp, rt, 0.3414, -6.5851, 0.2130, -8.5854);
+
+        (7.2 * rt.powf(2.8) / (f * f + 0.34 * rp * rp * rt.powf(1.6))
+            + 0.62 * xi_3 / ((54.0 - f).powf(1.16 * xi_1) + 0.83 * xi_2))
+            * f
+            * f
+            * rp
+            * rp
+            * 1.0e-3
+    } else if f <= 60.0 {
+        // interpolate the log of the line strengths
+        (gamma_54.ln() / 24.0 * (f - 58.0) * (f - 60.0) - gamma_58.ln() / 8.0 * (f - 54.0) * (f - 60.0)
+            + gamma_60.ln() / 12.0 * (f - 54.0) * (f - 58.0))
+            .exp()
+    } else if f <= 62.0 {
+        gamma_60 + (gamma_62 - gamma_60) * (f - 60.0) / 2.0
+    } else if f <= 66.0 {
+        (gamma_62.ln() / 8.0 * (f - 64.0) * (f - 66.0) - gamma_64.ln() / 4.0 * (f - 62.0) * (f - 66.0)
+            + gamma_66.ln() / 8.0 * (f - 62.0) * (f - 64.0))
+            .exp()
+    } else if f <= 120.0 {
+        let xi_4: f64 = phi(rp, rt, -0.0112, 0.0092, -0.1033, -0.0009);
+        let xi_5: f64 = phi(rp, rt, 0.2705, -2.7192, -0.3016, -4.1033);
+        let xi_6: f64 = phi(rp, rt, 0.2445, -5.9191, 0.0422, -8.0719);
+        let xi_7: f64 = phi(rp, rt, -0.1833, 6.5589, -0.2402, 6.131);
+
+        (3.02e-4 * rt.powf(3.5)
+            + 0.283 * rt.powf(3.8) / ((f - 118.75).powi(2) + 2.91 * rp * rp * rt.powf(1.6))
+            + 0.502 * xi_6 * (1.0 - 0.0163 * xi_7 * (f - 66.0))
+                / ((f - 66.0).powf(1.4346 * xi_4) + 1.15 * xi_5))
+            * f
+            * f
+            * rp
+            * rp
+            * 1.0e-3
+    } else {
+        let delta: f64 = -0.00306 * phi(rp, rt, 3.211, -14.94, 1.583, -16.37);
+
+        (3.02e-4 / (1.0 + 1.9e-5 * f.powf(1.5))
+            + 0.283 * rt.powf(0.3) / ((f - 118.75).powi(2) + 2.91 * rp * rp * rt.powf(1.6)))
+            * f
+            * f
+            * rp
+            * rp
+            * rt.powf(3.5)
+            * 1.0e-3
+            + delta
+    }
+}
+
+pub fn water_vapor_specific_attenuation_db_per_km(
+    frequency_ghz: f64,
+    water_vapor_density_g_m3: f64,
+    surface_pressure_hpa: f64,
+    temperature_k: f64,
+) -> f64 {
+    let f: f64 = frequency_ghz;
+    let rho: f64 = water_vapor_density_g_m3;
+    let rp: f64 = surface_pressure_hpa / 1013.0;
+    let rt: f64 = 288.0 / temperature_k;
+
+    let eta_1: f64 = 0.955 * rp * rt.powf(0.68) + 0.006 * rho;
+    let eta_2: f64 = 0.735 * rp * rt.sqrt() + 0.0353 * rt.powi(4) * rho;
+
+    // line shape correction
+    let g = |line_ghz: f64| 1.0 + ((f - line_ghz) / (f + line_ghz)).powi(2);
+
+    let lines: f64 = 3.98 * eta_1 * (2.23 * (1.0 - rt)).exp() / ((f - 22.235).powi(2) + 9.42 * eta_1 * eta_1)
+        * g(22.0)
+        + 11.96 * eta_1 * (0.7 * (1.0 - rt)).exp() / ((f - 183.31).powi(2) + 11.14 * eta_1 * eta_1)
+        + 0.081 * eta_1 * (6.44 * (1.0 - rt)).exp() / ((f - 321.226).powi(2) + 6.29 * eta_1 * eta_1)
+        + 3.66 * eta_1 * (1.6 * (1.0 - rt)).exp() / ((f - 325.153).powi(2) + 9.22 * eta_1 * eta_1)
+        + 25.37 * eta_1 * (1.09 * (1.0 - rt)).exp() / (f - 380.0).powi(2)
+        + 17.4 * eta_1 * (1.46 * (1.0 - rt)).exp() / (f - 448.0).powi(2)
+        + 844.6 * eta_1 * (0.17 * (1.0 - rt)).exp() / (f - 557.0).powi(2) * g(557.0)
+        + 290.0 * eta_1 * (0.41 * (1.0 - rt)).exp() / (f - 752.0).powi(2) * g(752.0)
+        + 8.3328e4 * eta_2 * (0.99 * (1.0 - rt)).exp() / (f - 1780.0).powi(2) * g(1780.0);
+
+    lines * f * f * rt.powf(2.5) * rho * 1.0e-4
+}
+
+pub fn gaseous_attenuation_db(
+    frequency_ghz: f64,
+    elevation_deg: f64,
+    water_vapor_density_g_m3: f64,
+    surface_pressure_hpa: f64,
+    temperature_k: f64,
+) -> f64 {
+    // zenith attenuation from equivalent heights, scaled by the cosecant of elevation
+    // The result can be used as LinkBudget::frequency_dependent_loss (alone or plus rain)
+    let rp: f64 = surface_pressure_hpa / 1013.0;
+
+    // water vapor equivalent height (km), taller near the absorption lines
+    let sigma_w: f64 = 1.013 / (1.0 + (-8.6 * (rp - 0.57)).exp());
+    let water_vapor_height_km: f64 = 1.66
+        * (1.0
+            + 1.39 * sigma_w / ((frequency_ghz - 22.235).powi(2) + 2.56 * sigma_w)
+            + 3.37 * sigma_w / ((frequency_ghz - 183.31).powi(2) + 4.69 * sigma_w)
+            + 1.58 * sigma_w / ((frequency_ghz - 325.1).powi(2) + 2.89 * sigma_w));
+
+    let zenith_attenuation: f64 = oxygen_specific_attenuation_db_per_km(frequency_ghz, surface_pressure_hpa, temperature_k)
+        * OXYGEN_EQUIVALENT_HEIGHT_KM
+        + water_vapor_specific_attenuation_db_per_km(
+            frequency_ghz,
+            water_vapor_density_g_m3,
+            surface_pressure_hpa,
+            temperature_k,
+        ) * water_vapor_height_km;
+
+    let elevation: f64 = elevation_deg.clamp(MINIMUM_ELEVATION_DEGREES, 90.0);
+
+    zenith_attenuation / crate::conversions::angle::degrees_to_radians(elevation).sin()
+}
+
+#[cfg(test)]
+mod tests {
+
+    // standard atmosphere at sea level
+    const WATER_VAPOR_DENSITY: f64 = 7.5; // g/m^3
+    const PRESSURE: f64 = 1013.25; // hPa
+    const TEMPERATURE: f64 = 288.15; // K
+
+    fn zenith(frequency_ghz: f64) -> f64 {
+        super::gaseous_attenuation_db(frequency_ghz, 90.0, WATER_VAPOR_DENSITY, PRESSURE, TEMPERATURE)
+    }
+
+    #[test]
+    fn oxygen_at_sixty_gigahertz() {
+        // ~15 dB/km at the center of the oxygen complex
+        let specific: f64 = super::oxygen_specific_attenuation_db_per_km(60.0, 1013.0, 288.0);
+
+        assert!((specific - 15.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn water_vapor_line() {
+        // local peak at the 22.235 GHz water vapor line
+        assert!(zenith(22.235) > zenith(18.0));
+        assert!(zenith(22.235) > zenith(26.0));
+    }
+
+    #[test]
+    fn oxygen_complex() {
+        // an order of magnitude or more above the surrounding bands
+        assert!(zenith(60.0) > 10.0 * zenith(50.0));
+        assert!(zenith(60.0) > 10.0 * zenith(75.0));
+    }
+
+    #[test]
+    fn ku_band_is_small() {
+        let attenuation: f64 = zenith(12.0);
+
+        assert!(attenuation > 0.0 && attenuation < 0.2);
+    }
+
+    #[test]
+    fn elevation_scaling() {
+        let zenith_attenuation: f64 = zenith(20.0);
+        let thirty_degrees: f64 =
+            super::gaseous_attenuation_db(20.0, 30.0, WATER_VAPOR_DENSITY, PRESSURE, TEMPERATURE);
+
+        // cosecant of 30 degrees is 2
+        assert!((thirty_degrees - 2.0 * zenith_attenuation).abs() < 1e-9);
+    }
+
+    #[test]
+    fn horizon_is_clamped() {
+        let horizon: f64 = super::gaseous_attenuation_db(20.0, 0.0, WATER_VAPOR_DENSITY, PRESSURE, TEMPERATURE);
+        let five_degrees: f64 = super::gaseous_attenuation_db(20.0, 5.0, WATER_VAPOR_DENSITY, PRESSURE, TEMPERATURE);
+
+        assert!(horizon.is_finite());
+        assert_eq!(five_degrees, horizon);
+    }
+}
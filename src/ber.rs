@@ -0,0 +1,962 @@
+use std::f64::consts::PI;
+
+use crate::modulation::Modulation;
+
+// DVB-S2 APSK rings as (points, radius relative to the inner ring, phase offset)
+// Ring ratios are the optimized values for mid code rates (ETSI EN 302 307)
+const APSK16_RINGS: [(u32, f64, f64); 2] = [(4, 1.0, PI / 4.0), (12, 2.7, PI / 12.0)];
+const APSK32_RINGS: [(u32, f64, f64); 3] = [(4, 1.0, PI / 4.0), (12, 2.84, PI / 12.0), (16, 5.27, 0.0)];
+
+pub fn erfc(x: f64) -> f64 {
+    // Complementary error function, accurate to ~1e-15 relative
+    // Maclaurin series of erf near zero, continued fraction in the tail where
+    // 1 - erf(x) would cancel catastrophically (BER below ~1e-7)
+    if x < 0.0 {
+        return 2.0 - erfc(-x);
+    }
+
+    if x < 2.0 {
+        return 1.0 - erf_series(x);
+    }
+
+    // erfc(x) = exp(-x^2)/sqrt(pi) * 1/(x + (1/2)/(x + 1/(x + (3/2)/(x + 2/(x + ...)))))
+    // evaluated from the back, 100 terms converges to machine precision for x >= 2
+    let mut fraction: f64 = x;
+    for n in (1..=100).rev() {
+        fraction = x + (n as f64 / 2.0) / fraction;
+    }
+
+    (-x * x).exp() / (std::f64::consts::PI.sqrt() * fraction)
+}
+
+fn erf_series(x: f64) -> f64 {
+    // erf(x) = 2/sqrt(pi) * sum (-1)^n * x^(2n+1) / (n! * (2n+1))
+    let x_squared: f64 = x * x;
+    let mut power_over_factorial: f64 = x; // (-1)^n * x^(2n+1) / n!
+    let mut sum: f64 = x;
+
+    for n in 1..100 {
+        power_over_factorial *= -x_squared / n as f64;
+        let term: f64 = power_over_factorial / (2 * n + 1) as f64;
+        sum += term;
+
+        if term.abs() < 1e-17 * sum.abs() {
+            break;
+        }
+    }
+
+    2.0 / std::f64::consts::PI.sqrt() * sum
+}
+
+pub fn q_function(x: f64) -> f64 {
+    // Gaussian tail probability, Q(x) = 0.5 * erfc(x / sqrt(2))
+    0.5 * erfc(x / std::f64::consts::SQRT_2)
+}
+
+pub fn ber_bpsk(eb_no_linear: f64) -> f64 {
+    // Pb = Q(sqrt(2 * Eb/No))
+    q_function((2.0 * eb_no_linear).sqrt())
+}
+
+pub fn ber_qpsk(eb_no_linear: f64) -> f64 {
+    // Gray coded QPSK is two orthogonal BPSK channels
+    ber_bpsk(eb_no_linear)
+}
+
+pub fn ber_mpsk(eb_no_linear: f64, m: u32) -> f64 {
+    if m <= 4 {
+        return ber_bpsk(eb_no_linear);
+    }
+
+    // Gray coded nearest neighbor approximation
+    // Pb ~= (2/k) * Q(sqrt(2 * k * Eb/No) * sin(pi/M))
+    let k: f64 = m.ilog2() as f64;
+    let argument: f64 = (2.0 * k * eb_no_linear).sqrt() * (std::f64::consts::PI / m as f64).sin();
+
+    (2.0 / k) * q_function(argument)
+}
+
+pub fn ber_mqam(eb_no_linear: f64, m: u32) -> f64 {
+    if m <= 4 {
+        return ber_bpsk(eb_no_linear);
+    }
+
+    if m == 8 {
+        // rectangular 4x2 grid, not a cross: Es = 1.5 * d^2 and ~2.5 nearest neighbors
+        // (a 4-PAM rail and a 2-PAM rail), so d^2 / (2 * No) = Eb/No
+        // Pb ~= (5/6) * Q(sqrt(Eb/No))
+        return (5.0 / 6.0) * q_function(eb_no_linear.sqrt());
+    }
+
+    let k: f64 = m.ilog2() as f64;
+
+    if m >= 32 && m.ilog2() % 2 == 1 {
+        // odd k (32, 128, ...) is a cross constellation, a sqrt(2M) square grid with the
+        // corners removed. Average energy is (31M/32 - 1) * d^2 / 6 against (M - 1) * d^2 / 6
+        // for square QAM, with ~4 * (1 - 1/sqrt(2M)) nearest neighbors per point
+        // Pb ~= (4/k) * (1 - 1/sqrt(2M)) * Q(sqrt(3 * k * Eb/No / (31M/32 - 1)))
+        let m: f64 = m as f64;
+        let argument: f64 = (3.0 * k * eb_no_linear / (31.0 * m / 32.0 - 1.0)).sqrt();
+
+        return (4.0 / k) * (1.0 - 1.0 / (2.0 * m).sqrt()) * q_function(argument);
+    }
+
+    // Gray coded square QAM
+    // Pb ~= (4/k) * (1 - 1/sqrt(M)) * Q(sqrt(3 * k * Eb/No / (M - 1)))
+    let m: f64 = m as f64;
+    let argument: f64 = (3.0 * k * eb_no_linear / (m - 1.0)).sqrt();
+
+    (4.0 / k) * (1.0 - 1.0 / m.sqrt()) * q_function(argument)
+}
+
+pub fn ber_apsk(eb_no_linear: f64, m: u32) -> f64 {
+    // Union bound over the constellation's pairwise distances, dominated by the
+    // nearest neighbors, with Gray-like labeling so Pb ~= Ps / k.
+    // Assumes the optimized DVB-S2 ring ratios. Other orders have no ring table and
+    // return NaN rather than borrowing a QAM answer, see Modulation::try_apsk
+    let Some(pairs) = apsk_pairwise_gains(m) else {
+        return f64::NAN;
+    };
+
+    // Ps <= (1/M) * sum_i sum_j!=i Q(sqrt(b_ij * Eb/No))
+    let k: f64 = m.ilog2() as f64;
+    let symbol_error_rate: f64 = pairs.iter().map(|b| q_function((b * eb_no_linear).sqrt())).sum::<f64>() / m as f64;
+
+    symbol_error_rate / k
+}
+
+fn apsk_pairwise_gains(m: u32) -> Option<Vec<f64>> {
+    // b_ij for every ordered pair of distinct APSK points, so that the pairwise error
+    // Q(d_ij / sqrt(2 * No)) = Q(sqrt(b_ij * Eb/No)), None without a ring table
+    let rings: &[(u32, f64, f64)] = match m {
+        16 => &APSK16_RINGS,
+        32 => &APSK32_RINGS,
+        _ => return None,
+    };
+
+    let points: Vec<(f64, f64)> = rings
+        .iter()
+        .flat_map(|(count, radius, phase)| {
+            (0..*count).map(move |i| {
+                let angle: f64 = phase + 2.0 * PI * i as f64 / *count as f64;
+                (radius * angle.cos(), radius * angle.sin())
+            })
+        })
+        .collect();
+
+    // No = Es / (k * Eb/No), so d^2 / (2 * No) = d^2 * k / (2 * Es) * Eb/No
+    let k: f64 = m.ilog2() as f64;
+    let average_energy: f64 = points.iter().map(|(x, y)| x * x + y * y).sum::<f64>() / m as f64;
+
+    let mut gains: Vec<f64> = Vec::with_capacity(points.len() * (points.len() - 1));
+    for (i, (xi, yi)) in points.iter().enumerate() {
+        for (j, (xj, yj)) in points.iter().enumerate() {
+            if i != j {
+                let distance_squared: f64 = (xi - xj).powi(2) + (yi - yj).powi(2);
+                gains.push(distance_squared * k / (2.0 * average_energy));
+            }
+        }
+    }
+
+    Some(gains)
+}
+
+// Symbol error rates take Es/No (energy per symbol), not Eb/No
+
+pub fn ser_mpsk(es_no_linear: f64, m: u32) -> f64 {
+    match m {
+        // antipodal, every symbol error is a bit error
+        0..=2 => q_function((2.0 * es_no_linear).sqrt()),
+        // exact for QPSK, 2Q - Q^2
+        4 => {
+            let q: f64 = q_function(es_no_linear.sqrt());
+            2.0 * q - q * q
+        }
+        // nearest neighbor union bound, Ps ~= 2 * Q(sqrt(2 * Es/No) * sin(pi/M))
+        _ => 2.0 * q_function((2.0 * es_no_linear).sqrt() * (PI / m as f64).sin()),
+    }
+}
+
+pub fn ser_mqam(es_no_linear: f64, m: u32) -> f64 {
+    if m <= 2 {
+        // antipodal
+        return q_function((2.0 * es_no_linear).sqrt());
+    }
+
+    if m == 8 {
+        // rectangular 4x2 grid as in ber_mqam, a 4-PAM and a 2-PAM rail with Es = 1.5 * d^2
+        // Ps = 1 - (1 - 1.5 * q) * (1 - q), q = Q(sqrt(Es/No / 3))
+        let q: f64 = q_function((es_no_linear / 3.0).sqrt());
+        return 1.0 - (1.0 - 1.5 * q) * (1.0 - q);
+    }
+
+    if m >= 32 && m.ilog2() % 2 == 1 {
+        // cross constellation as in ber_mqam, the rails aren't independent so use the
+        // nearest neighbor bound, Ps ~= 4 * (1 - 1/sqrt(2M)) * Q(sqrt(3 * Es/No / (31M/32 - 1)))
+        let m: f64 = m as f64;
+        let argument: f64 = (3.0 * es_no_linear / (31.0 * m / 32.0 - 1.0)).sqrt();
+
+        return 4.0 * (1.0 - 1.0 / (2.0 * m).sqrt()) * q_function(argument);
+    }
+
+    // square QAM as two independent sqrt(M)-ary PAM rails
+    // Ps = 1 - (1 - P_sqrt(M))^2, P_sqrt(M) = 2 * (1 - 1/sqrt(M)) * Q(sqrt(3 * Es/No / (M - 1)))
+    let m: f64 = m as f64;
+    let rail: f64 = 2.0 * (1.0 - 1.0 / m.sqrt()) * q_function((3.0 * es_no_linear / (m - 1.0)).sqrt());
+
+    1.0 - (1.0 - rail).powi(2)
+}
+
+pub fn ber_from_ser_gray(ser: f64, bits_per_symbol: u32) -> f64 {
+    // Gray coding, a symbol error is almost always a single bit error
+    ser / bits_per_symbol as f64
+}
+
+pub fn ber(eb_no_linear: f64, modulation: &Modulation) -> f64 {
+    // AWGN bit error rate, capped at 0.5 (a coin flip)
+    let ber: f64 = match modulation {
+        Modulation::Bpsk => ber_bpsk(eb_no_linear),
+        Modulation::Qpsk => ber_qpsk(eb_no_linear),
+        // coherently detected MSK performs like BPSK
+        Modulation::Msk => ber_bpsk(eb_no_linear),
+        Modulation::Mpsk(m) => ber_mpsk(eb_no_linear, *m),
+        Modulation::Mqam(m) => ber_mqam(eb_no_linear, *m),
+        Modulation::Apsk(m) => ber_apsk(eb_no_linear, *m),
+    };
+
+    // f64::min would turn the NaN of an unmodeled order into 0.5
+    if ber.is_nan() {
+        return ber;
+    }
+    ber.min(0.5)
+}
+
+pub fn ber_from_db(eb_no_db: f64, modulation: &Modulation) -> f64 {
+    ber(10.0_f64.powf(eb_no_db / 10.0), modulation)
+}
+
+pub fn ber_curve(modulation: &Modulation, eb_no_db_start: f64, eb_no_db_stop: f64, step_db: f64) -> Vec<(f64, f64)> {
+    // (eb_no_db, ber) points from start to stop (inclusive) for plotting waterfall curves
+    if step_db <= 0.0 || eb_no_db_stop < eb_no_db_start {
+        return Vec::new();
+    }
+
+    // step by index so floating point error doesn't accumulate
+    let steps: usize = ((eb_no_db_stop - eb_no_db_start) / step_db + 1e-9).floor() as usize;
+
+    (0..=steps)
+        .map(|i| {
+            let eb_no_db: f64 = eb_no_db_start + i as f64 * step_db;
+            (eb_no_db, ber_from_db(eb_no_db, modulation))
+        })
+        .collect()
+}
+
+// Rayleigh fading, BER averaged over the exponential distribution of the instantaneous Eb/No
+
+fn rayleigh_average(a: f64, b: f64, average_eb_no_linear: f64) -> f64 {
+    // E[a * Q(sqrt(b * gamma))] = (a/2) * (1 - sqrt((b*gamma/2) / (1 + b*gamma/2)))
+    let half: f64 = b * average_eb_no_linear / 2.0;
+    (a / 2.0) * (1.0 - (half / (1.0 + half)).sqrt())
+}
+
+pub fn ber_bpsk_rayleigh(avg_eb_no_linear: f64) -> f64 {
+    // 0.5 * (1 - sqrt(gamma / (1 + gamma))), ~1/(4*gamma) at high SNR
+    0.5 * (1.0 - (avg_eb_no_linear / (1.0 + avg_eb_no_linear)).sqrt())
+}
+
+pub fn ber_bpsk_rician(avg_eb_no_linear: f64, k_factor: f64) -> f64 {
+    // BPSK/QPSK over Rician fading, K is the ratio of specular (line of sight) to diffuse power
+    // K = 0 is Rayleigh, K -> infinity is AWGN
+    // Craig's form of Q averaged with the Rician MGF, integrated with Simpson's rule:
+    // Pb = (1/pi) * int_0^(pi/2) M(-1/sin^2(t)) dt
+    // M(s) = (1 + K) / (1 + K - s*g) * exp(K*s*g / (1 + K - s*g))
+    const INTERVALS: usize = 1000;
+
+    let k: f64 = k_factor.max(0.0);
+    let integrand = |theta: f64| {
+        let sin_squared: f64 = theta.sin().powi(2);
+        if sin_squared == 0.0 {
+            return 0.0;
+        }
+        // multiplied through by sin^2 to keep the terms finite near theta = 0
+        let denominator: f64 = (1.0 + k) * sin_squared + avg_eb_no_linear;
+        (1.0 + k) * sin_squared / denominator * (-k * avg_eb_no_linear / denominator).exp()
+    };
+
+    let h: f64 = (PI / 2.0) / INTERVALS as f64;
+    let mut sum: f64 = integrand(0.0) + integrand(PI / 2.0);
+    for i in 1..INTERVALS {
+        let weight: f64 = if i % 2 == 1 { 4.0 } else { 2.0 };
+        sum += weight * integrand(i as f64 * h);
+    }
+
+    (sum * h / 3.0 / PI).min(0.5)
+}
+
+pub fn ber_mpsk_rayleigh(avg_eb_no_linear: f64, m: u32) -> f64 {
+    if m <= 4 {
+        return ber_bpsk_rayleigh(avg_eb_no_linear);
+    }
+
+    let k: f64 = m.ilog2() as f64;
+    rayleigh_average(2.0 / k, 2.0 * k * (PI / m as f64).sin().powi(2), avg_eb_no_linear)
+}
+
+pub fn ber_mqam_rayleigh(avg_eb_no_linear: f64, m: u32) -> f64 {
+    if m <= 4 {
+        return ber_bpsk_rayleigh(avg_eb_no_linear);
+    }
+
+    if m == 8 {
+        // rectangular 4x2 grid, (5/6) * Q(sqrt(Eb/No)) as in ber_mqam
+        return rayleigh_average(5.0 / 6.0, 1.0, avg_eb_no_linear);
+    }
+
+    let k: f64 = m.ilog2() as f64;
+
+    if m >= 32 && m.ilog2() % 2 == 1 {
+        // cross constellation, (31M/32 - 1) energy as in ber_mqam
+        let m: f64 = m as f64;
+        return rayleigh_average(
+            (4.0 / k) * (1.0 - 1.0 / (2.0 * m).sqrt()),
+            3.0 * k / (31.0 * m / 32.0 - 1.0),
+            avg_eb_no_linear,
+        );
+    }
+
+    let m: f64 = m as f64;
+    rayleigh_average((4.0 / k) * (1.0 - 1.0 / m.sqrt()), 3.0 * k / (m - 1.0), avg_eb_no_linear)
+}
+
+pub fn ber_apsk_rayleigh(avg_eb_no_linear: f64, m: u32) -> f64 {
+    // the same union bound as ber_apsk with each pairwise term averaged over the fading,
+    // NaN for orders without a ring table
+    let Some(pairs) = apsk_pairwise_gains(m) else {
+        return f64::NAN;
+    };
+
+    let a: f64 = 1.0 / (m as f64 * m.ilog2() as f64);
+    pairs.iter().map(|b| rayleigh_average(a, *b, avg_eb_no_linear)).sum()
+}
+
+pub fn ber_rayleigh(avg_eb_no_linear: f64, modulation: &Modulation) -> f64 {
+    let ber: f64 = match modulation {
+        Modulation::Bpsk | Modulation::Qpsk | Modulation::Msk => ber_bpsk_rayleigh(avg_eb_no_linear),
+        Modulation::Mpsk(m) => ber_mpsk_rayleigh(avg_eb_no_linear, *m),
+        Modulation::Mqam(m) => ber_mqam_rayleigh(avg_eb_no_linear, *m),
+        Modulation::Apsk(m) => ber_apsk_rayleigh(avg_eb_no_linear, *m),
+    };
+
+    // f64::min would turn the NaN of an unmodeled order into 0.5
+    if ber.is_nan() {
+        return ber;
+    }
+    ber.min(0.5)
+}
+
+pub fn q_function_inverse(p: f64) -> f64 {
+    // x such that Q(x) = p, for 0 < p < 1
+    // Acklam's rational approximation of the normal quantile (~1e-9 relative),
+    // then one Halley step against erfc for full double precision
+    const A: [f64; 6] = [
+        -3.969683028665376e1,
+        2.209460984245205e2,
+        -2.759285104469687e2,
+        1.38357751867269e2,
+        -3.066479806614716e1,
+        2.506628277459239,
+    ];
+    const B: [f64; 5] = [
+        -5.447609879822406e1,
+        1.615858368580409e2,
+        -1.556989798598866e2,
+        6.680131188771972e1,
+        -1.328068155288572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784894002430293e-3,
+        -3.223964580411365e-1,
+        -2.400758277161838,
+        -2.549732539343734,
+        4.374664141464968,
+        2.938163982698783,
+    ];
+    const D: [f64; 4] = [
+        7.784695709041462e-3,
+        3.224671290700398e-1,
+        2.445134137142996,
+        3.754408661907416,
+    ];
+    const P_LOW: f64 = 0.02425;
+
+    // lower tail quantile of 1 - p, which is Q^-1(p)
+    let lower: f64 = 1.0 - p;
+    let tail = |q: f64| {
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    };
+
+    let mut x: f64 = if p < P_LOW {
+        // upper tail, computed from p directly to keep precision
+        -tail((-2.0 * p.ln()).sqrt())
+    } else if p > 1.0 - P_LOW {
+        tail((-2.0 * lower.ln()).sqrt())
+    } else {
+        let q: f64 = lower - 0.5;
+        let r: f64 = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    };
+
+    // Halley refinement on Q(x) - p
+    let error: f64 = q_function(x) - p;
+    let u: f64 = -error * (2.0 * PI).sqrt() * (x * x / 2.0).exp();
+    x -= u / (1.0 + x * u / 2.0);
+
+    x
+}
+
+// default search bracket for required_eb_no_db (dB)
+const REQUIRED_EB_NO_LOW_DB: f64 = -5.0;
+const REQUIRED_EB_NO_HIGH_DB: f64 = 50.0;
+
+// bisection stops once the BER is within this relative error of the target, or after
+// REQUIRED_EB_NO_MAX_ITERATIONS halvings of the bracket
+const REQUIRED_EB_NO_TOLERANCE: f64 = 1e-6;
+const REQUIRED_EB_NO_MAX_ITERATIONS: usize = 100;
+
+pub fn required_eb_no_db(target_ber: f64, modulation: &Modulation) -> Option<f64> {
+    required_eb_no_db_in_range(target_ber, modulation, REQUIRED_EB_NO_LOW_DB, REQUIRED_EB_NO_HIGH_DB)
+}
+
+pub fn required_eb_no_db_in_range(target_ber: f64, modulation: &Modulation, low_db: f64, high_db: f64) -> Option<f64> {
+    required_eb_no_db_with_opts(
+        target_ber,
+        modulation,
+        low_db,
+        high_db,
+        REQUIRED_EB_NO_TOLERANCE,
+        REQUIRED_EB_NO_MAX_ITERATIONS,
+    )
+}
+
+pub fn required_eb_no_db_with_opts(
+    target_ber: f64,
+    modulation: &Modulation,
+    low_db: f64,
+    high_db: f64,
+    tolerance: f64,
+    max_iterations: usize,
+) -> Option<f64> {
+    // Eb/No (dB) that achieves the target BER, searched within [low_db, high_db]
+    // None when the target can't be reached inside the bracket (BER above the curve at
+    // low_db, e.g. 0.6, or below it at high_db), widen the bracket for extreme targets
+    // tolerance (relative BER error) and max_iterations only apply to the bisection,
+    // BPSK, QPSK and MSK are solved in closed form
+    if target_ber.is_nan() || target_ber <= 0.0 || low_db >= high_db {
+        return None;
+    }
+    // NaN when the modulation has no BER model, e.g. Apsk(64)
+    let (ber_low, ber_high) = (ber_from_db(low_db, modulation), ber_from_db(high_db, modulation));
+    if ber_low.is_nan() || ber_high.is_nan() || target_ber > ber_low || target_ber < ber_high {
+        return None;
+    }
+
+    match modulation {
+        // Pb = Q(sqrt(2 * Eb/No)) inverts in closed form, Eb/No = Q^-1(Pb)^2 / 2
+        Modulation::Bpsk | Modulation::Qpsk | Modulation::Msk if target_ber < 0.5 => {
+            let x: f64 = q_function_inverse(target_ber);
+            Some(10.0 * (x * x / 2.0).log10())
+        }
+        _ => Some(required_eb_no_db_bisection(
+            target_ber,
+            modulation,
+            (low_db, high_db),
+            tolerance,
+            max_iterations,
+        )),
+    }
+}
+
+fn required_eb_no_db_bisection(
+    target_ber: f64,
+    modulation: &Modulation,
+    bracket_db: (f64, f64),
+    tolerance: f64,
+    max_iterations: usize,
+) -> f64 {
+    // Bisection search for the Eb/No (dB) that achieves the target BER
+    // BER decreases monotonically with Eb/No, and the caller has checked the target is bracketed
+    let (mut low, mut high) = bracket_db;
+
+    for _ in 0..max_iterations {
+        let mid: f64 = (low + high) / 2.0;
+        let ber_mid: f64 = ber_from_db(mid, modulation);
+
+        if (ber_mid - target_ber).abs() / target_ber < tolerance {
+            return mid;
+        }
+
+        if ber_mid > target_ber {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+
+    (low + high) / 2.0
+}
+
+fn splitmix64(state: &mut u64) -> u64 {
+    // small, seedable generator so simulations are reproducible without an RNG crate
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z: u64 = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+fn uniform(state: &mut u64) -> f64 {
+    // (0, 1], never 0 so the logarithm in Box-Muller stays finite
+    ((splitmix64(state) >> 11) as f64 + 1.0) / (1u64 << 53) as f64
+}
+
+pub fn monte_carlo_ber_bpsk(eb_no_db: f64, num_bits: usize, seed: u64) -> f64 {
+    // empirical BPSK BER over AWGN, for validating the analytic curves, not for budgets
+    // Each bit is sent as +1 with Gaussian noise of variance No/2 = 1 / (2 * Eb/No)
+    // (Box-Muller), the bit is in error when the sample crosses zero
+    if num_bits == 0 {
+        return 0.0;
+    }
+
+    let sigma: f64 = (1.0 / (2.0 * 10.0_f64.powf(eb_no_db / 10.0))).sqrt();
+    let mut state: u64 = seed;
+    let mut errors: usize = 0;
+
+    // Box-Muller gives two independent samples per pair of uniforms
+    let mut bit: usize = 0;
+    while bit < num_bits {
+        let radius: f64 = (-2.0 * uniform(&mut state).ln()).sqrt();
+        let angle: f64 = 2.0 * std::f64::consts::PI * uniform(&mut state);
+
+        for noise in [radius * angle.cos(), radius * angle.sin()] {
+            if bit < num_bits && 1.0 + sigma * noise < 0.0 {
+                errors += 1;
+            }
+            bit += 1;
+        }
+    }
+
+    errors as f64 / num_bits as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::modulation::Modulation;
+
+    #[test]
+    fn erfc() {
+        assert_eq!(1.0, super::erfc(0.0));
+        assert!((super::erfc(1.0) - 0.157_299_207_050_285_1).abs() < 1e-15);
+        assert!((super::erfc(-1.0) - 1.842_700_792_949_715).abs() < 1e-15);
+    }
+
+    #[test]
+    fn erfc_deep_tail() {
+        // reference values, compared relative to their size
+        let references: [(f64, f64); 5] = [
+            (0.5, 4.795_001_221_869_535e-1),
+            (2.0, 4.677_734_981_047_266e-3),
+            (3.0, 2.209_049_699_858_544e-5),
+            (4.0, 1.541_725_790_028_002e-8),
+            (5.0, 1.537_459_794_428_035e-12),
+        ];
+
+        for (x, expected) in references {
+            let relative_error: f64 = (super::erfc(x) - expected).abs() / expected;
+            assert!(relative_error < 1e-12, "erfc({}) relative error {}", x, relative_error);
+        }
+    }
+
+    #[test]
+    fn required_eb_no_bpsk_one_in_a_billion() {
+        let eb_no_db: f64 = super::required_eb_no_db(1e-9, &Modulation::Bpsk).unwrap();
+
+        assert!((eb_no_db - 12.6).abs() < 0.1);
+    }
+
+    #[test]
+    fn q_function() {
+        // Q(0) is half of the distribution
+        assert_eq!(0.5, super::q_function(0.0));
+    }
+
+    #[test]
+    fn bpsk_at_nine_point_six_db() {
+        // textbook: BPSK needs ~9.6 dB for a BER of 1e-5
+        let ber: f64 = super::ber_from_db(9.6, &Modulation::Bpsk);
+
+        assert!(ber > 0.9e-5 && ber < 1.1e-5);
+    }
+
+    #[test]
+    fn qpsk_matches_bpsk() {
+        assert_eq!(
+            super::ber_from_db(6.0, &Modulation::Bpsk),
+            super::ber_from_db(6.0, &Modulation::Qpsk)
+        );
+    }
+
+    #[test]
+    fn higher_order_needs_more_energy() {
+        let qpsk: f64 = super::ber_from_db(10.0, &Modulation::Qpsk);
+        let psk8: f64 = super::ber_from_db(10.0, &Modulation::Mpsk(8));
+        let qam16: f64 = super::ber_from_db(10.0, &Modulation::Mqam(16));
+        let qam64: f64 = super::ber_from_db(10.0, &Modulation::Mqam(64));
+
+        assert!(qpsk < psk8);
+        assert!(qpsk < qam16);
+        assert!(qam16 < qam64);
+    }
+
+    #[test]
+    fn required_eb_no_bpsk() {
+        let eb_no_db: f64 = super::required_eb_no_db(1e-5, &Modulation::Bpsk).unwrap();
+
+        assert!((eb_no_db - 9.59).abs() < 0.01);
+    }
+
+    #[test]
+    fn required_eb_no_round_trip() {
+        let modulation = Modulation::Mqam(16);
+        let eb_no_db: f64 = super::required_eb_no_db(1e-6, &modulation).unwrap();
+        let ber: f64 = super::ber_from_db(eb_no_db, &modulation);
+
+        assert!((ber - 1e-6).abs() / 1e-6 < 1e-3);
+    }
+
+    #[test]
+    fn apsk16_needs_more_than_qam16() {
+        // ring APSK trades a little minimum distance for amplifier friendliness
+        let apsk: f64 = super::required_eb_no_db(1e-6, &Modulation::Apsk(16)).unwrap();
+        let qam: f64 = super::required_eb_no_db(1e-6, &Modulation::Mqam(16)).unwrap();
+
+        assert!(apsk > qam);
+        assert!(apsk - qam < 1.5);
+    }
+
+    #[test]
+    fn apsk32_needs_more_than_apsk16() {
+        let apsk16: f64 = super::required_eb_no_db(1e-6, &Modulation::Apsk(16)).unwrap();
+        let apsk32: f64 = super::required_eb_no_db(1e-6, &Modulation::Apsk(32)).unwrap();
+
+        assert!(apsk32 > apsk16);
+    }
+
+    #[test]
+    fn ber_curve() {
+        let curve = super::ber_curve(&Modulation::Qpsk, 0.0, 10.0, 0.5);
+
+        assert_eq!(21, curve.len());
+        assert_eq!(0.0, curve[0].0);
+        assert_eq!(10.0, curve[20].0);
+
+        // waterfall, BER falls with every step
+        for window in curve.windows(2) {
+            assert!(window[1].1 < window[0].1);
+        }
+    }
+
+    #[test]
+    fn ber_curve_invalid_range() {
+        assert!(super::ber_curve(&Modulation::Qpsk, 0.0, 10.0, 0.0).is_empty());
+        assert!(super::ber_curve(&Modulation::Qpsk, 0.0, 10.0, -1.0).is_empty());
+        assert!(super::ber_curve(&Modulation::Qpsk, 10.0, 0.0, 1.0).is_empty());
+    }
+
+    #[test]
+    fn ser_exceeds_ber_16qam() {
+        // same Es/No of 16 dB, 4 bits per symbol
+        let es_no: f64 = 10.0_f64.powf(16.0 / 10.0);
+
+        let ser: f64 = super::ser_mqam(es_no, 16);
+        let ber: f64 = super::ber_mqam(es_no / 4.0, 16);
+
+        assert!(ser > ber);
+        assert!((super::ber_from_ser_gray(ser, 4) - ber).abs() / ber < 0.05);
+    }
+
+    #[test]
+    fn qam_ser_non_square_orders() {
+        // Gray coded at high SNR the symbol errors are single bit errors, so SER / k
+        // follows ber_mqam for the rectangular 8 and cross 32 and 128 orders too
+        let eb_no: f64 = 10.0_f64.powf(14.0 / 10.0);
+
+        for m in [8_u32, 32, 128] {
+            let k: u32 = m.ilog2();
+            let ser: f64 = super::ser_mqam(k as f64 * eb_no, m);
+            let ber: f64 = super::ber_mqam(eb_no, m);
+
+            assert!((super::ber_from_ser_gray(ser, k) - ber).abs() / ber < 0.01);
+        }
+    }
+
+    #[test]
+    fn qpsk_ser_is_twice_ber() {
+        // high SNR, Es/No = 2 * Eb/No for QPSK
+        let es_no: f64 = 10.0_f64.powf(14.0 / 10.0);
+
+        let ser: f64 = super::ser_mpsk(es_no, 4);
+        let ber: f64 = super::ber_qpsk(es_no / 2.0);
+
+        assert!((ser / ber - 2.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn bpsk_ser_is_ber() {
+        let eb_no: f64 = 10.0_f64.powf(6.0 / 10.0);
+
+        assert_eq!(super::ber_bpsk(eb_no), super::ser_mpsk(eb_no, 2));
+    }
+
+    #[test]
+    fn rayleigh_decays_inversely() {
+        let twenty_db: f64 = super::ber_bpsk_rayleigh(100.0);
+        let thirty_db: f64 = super::ber_bpsk_rayleigh(1000.0);
+
+        // ~1/(4*gamma), ten times the SNR is a tenth of the BER
+        assert!((twenty_db - 1.0 / 400.0).abs() / twenty_db < 0.02);
+        assert!((twenty_db / thirty_db - 10.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn rayleigh_far_worse_than_awgn() {
+        let eb_no: f64 = 100.0; // 20 dB
+
+        let awgn: f64 = super::ber(eb_no, &Modulation::Qpsk);
+        let rayleigh: f64 = super::ber_rayleigh(eb_no, &Modulation::Qpsk);
+
+        assert!(rayleigh > 1e-3);
+        assert!(awgn < 1e-20);
+    }
+
+    #[test]
+    fn rayleigh_higher_order() {
+        let eb_no: f64 = 100.0;
+
+        let qpsk: f64 = super::ber_rayleigh(eb_no, &Modulation::Qpsk);
+        let psk8: f64 = super::ber_rayleigh(eb_no, &Modulation::Mpsk(8));
+        let qam16: f64 = super::ber_rayleigh(eb_no, &Modulation::Mqam(16));
+
+        assert!(qpsk < psk8);
+        assert!(qpsk < qam16);
+    }
+
+    fn rayleigh_numeric(awgn: impl Fn(f64) -> f64, avg: f64) -> f64 {
+        // integrate an (uncapped) AWGN BER over the exponential Eb/No distribution,
+        // gamma = avg * u^2 to smooth the sqrt at zero, truncated at u = 3 where the
+        // fading weight exp(-9) times the AWGN BER is negligible
+        let intervals: usize = 1200;
+        let h: f64 = 3.0 / intervals as f64;
+
+        let integrand = |u: f64| awgn(avg * u * u) * (-u * u).exp() * 2.0 * u;
+        let mut sum: f64 = integrand(0.0) + integrand(3.0);
+        for i in 1..intervals {
+            let weight: f64 = if i % 2 == 1 { 4.0 } else { 2.0 };
+            sum += weight * integrand(i as f64 * h);
+        }
+
+        sum * h / 3.0
+    }
+
+    #[test]
+    fn rayleigh_apsk_averages_the_awgn_curve() {
+        let avg: f64 = 100.0;
+
+        for m in [16, 32] {
+            let numeric: f64 = rayleigh_numeric(|eb_no| super::ber_apsk(eb_no, m), avg);
+            let rayleigh: f64 = super::ber_rayleigh(avg, &Modulation::Apsk(m));
+
+            assert!((rayleigh - numeric).abs() / numeric < 1e-6);
+        }
+
+        // no ring table, no answer on either path
+        assert!(super::ber(avg, &Modulation::Apsk(64)).is_nan());
+        assert!(super::ber_rayleigh(avg, &Modulation::Apsk(64)).is_nan());
+    }
+
+    #[test]
+    fn rayleigh_qam_averages_the_awgn_curve() {
+        // square, rectangular 8 and cross orders all follow their AWGN forms
+        let avg: f64 = 100.0;
+
+        for m in [8, 16, 32, 64, 128] {
+            let numeric: f64 = rayleigh_numeric(|eb_no| super::ber_mqam(eb_no, m), avg);
+            let rayleigh: f64 = super::ber_mqam_rayleigh(avg, m);
+
+            assert!((rayleigh - numeric).abs() / numeric < 1e-6);
+        }
+    }
+
+    #[test]
+    fn q_function_inverse() {
+        for p in [0.4, 0.1, 0.02, 1e-3, 1e-6, 1e-9, 1e-12] {
+            let x: f64 = super::q_function_inverse(p);
+            assert!((super::q_function(x) - p).abs() / p < 1e-12);
+        }
+    }
+
+    #[test]
+    fn closed_form_matches_bisection() {
+        let targets: [f64; 8] = [1e-2, 1e-3, 1e-4, 1e-5, 1e-6, 1e-7, 1e-8, 1e-9];
+
+        for modulation in [Modulation::Bpsk, Modulation::Qpsk, Modulation::Msk] {
+            for target in targets {
+                let closed_form: f64 = super::required_eb_no_db(target, &modulation).unwrap();
+                let bisection: f64 = super::required_eb_no_db_bisection(target, &modulation, (-5.0, 50.0), 1e-6, 100);
+
+                assert!((closed_form - bisection).abs() < 0.01);
+            }
+        }
+    }
+
+    #[test]
+    fn required_eb_no_unachievable() {
+        // BPSK never gets worse than 0.5, and the bracket floor is ~0.21
+        assert_eq!(None, super::required_eb_no_db(0.6, &Modulation::Bpsk));
+        assert_eq!(None, super::required_eb_no_db(0.6, &Modulation::Mqam(16)));
+        assert_eq!(None, super::required_eb_no_db(0.0, &Modulation::Mqam(16)));
+
+        // 1e-30 needs ~18 dB of BPSK, out of reach of a 15 dB ceiling
+        assert_eq!(None, super::required_eb_no_db_in_range(1e-30, &Modulation::Bpsk, -5.0, 15.0));
+        assert_eq!(None, super::required_eb_no_db_in_range(1e-30, &Modulation::Mqam(64), -5.0, 15.0));
+    }
+
+    #[test]
+    fn required_eb_no_deep_target() {
+        // the accurate erfc keeps 1e-30 representable, so the default bracket reaches it
+        let eb_no_db: f64 = super::required_eb_no_db(1e-30, &Modulation::Bpsk).unwrap();
+        let ber: f64 = super::ber_from_db(eb_no_db, &Modulation::Bpsk);
+
+        assert!((ber - 1e-30).abs() / 1e-30 < 1e-6);
+    }
+
+    #[test]
+    fn rician_limits() {
+        let eb_no: f64 = 10.0_f64.powf(10.0 / 10.0);
+
+        // K = 0 is Rayleigh
+        let rayleigh: f64 = super::ber_bpsk_rayleigh(eb_no);
+        assert!((super::ber_bpsk_rician(eb_no, 0.0) - rayleigh).abs() / rayleigh < 1e-9);
+
+        // a strong line of sight approaches AWGN
+        let awgn: f64 = super::ber_bpsk(eb_no);
+        assert!((super::ber_bpsk_rician(eb_no, 1.0e6) - awgn).abs() / awgn < 1e-3);
+    }
+
+    #[test]
+    fn rician_monotonic_in_k() {
+        let eb_no: f64 = 10.0_f64.powf(10.0 / 10.0);
+        let awgn: f64 = super::ber_bpsk(eb_no);
+        let rayleigh: f64 = super::ber_bpsk_rayleigh(eb_no);
+
+        let mut previous: f64 = rayleigh;
+        for k_factor in [0.5, 1.0, 3.0, 10.0, 30.0] {
+            let ber: f64 = super::ber_bpsk_rician(eb_no, k_factor);
+
+            assert!(ber < previous);
+            assert!(ber > awgn);
+            previous = ber;
+        }
+    }
+
+    #[test]
+    fn monte_carlo_ber_bpsk() {
+        // at 6 dB the analytic BER is 2.39e-3, ~2400 errors in 1e6 bits
+        let bits: usize = 1_000_000;
+        let analytic: f64 = super::ber_from_db(6.0, &Modulation::Bpsk);
+        let simulated: f64 = super::monte_carlo_ber_bpsk(6.0, bits, 1);
+
+        // binomial standard deviation of the estimate, within 4 sigma
+        let sigma: f64 = (analytic * (1.0 - analytic) / bits as f64).sqrt();
+        assert!((simulated - analytic).abs() < 4.0 * sigma);
+    }
+
+    #[test]
+    fn monte_carlo_ber_bpsk_seeded() {
+        assert_eq!(
+            super::monte_carlo_ber_bpsk(3.0, 10_001, 42),
+            super::monte_carlo_ber_bpsk(3.0, 10_001, 42)
+        );
+        assert_eq!(0.0, super::monte_carlo_ber_bpsk(3.0, 0, 42));
+    }
+
+    #[test]
+    fn required_eb_no_db_beyond_default_bracket() {
+        // 1024-PSK at 1e-9 needs more than the default 50 dB
+        let modulation = Modulation::Mpsk(1024);
+        assert_eq!(None, super::required_eb_no_db(1e-9, &modulation));
+
+        let eb_no_db: f64 = super::required_eb_no_db_with_opts(1e-9, &modulation, -5.0, 80.0, 1e-6, 100).unwrap();
+
+        assert!(eb_no_db > 50.0);
+        assert!((super::ber_from_db(eb_no_db, &modulation) / 1e-9 - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn required_eb_no_db_with_coarse_tolerance() {
+        let modulation = Modulation::Mqam(1024);
+        let coarse: f64 = super::required_eb_no_db_with_opts(1e-9, &modulation, -5.0, 50.0, 1e-2, 100).unwrap();
+        let fine: f64 = super::required_eb_no_db(1e-9, &modulation).unwrap();
+
+        assert!((super::ber_from_db(coarse, &modulation) / 1e-9 - 1.0).abs() < 1e-2);
+        assert!((coarse - fine).abs() < 0.05);
+
+        // a single halving lands in the middle of the bracket
+        assert_eq!(Some(22.5), super::required_eb_no_db_with_opts(1e-9, &modulation, -5.0, 50.0, 1e-6, 0));
+    }
+
+    #[test]
+    fn cross_qam_between_square_orders() {
+        let qam16: f64 = super::required_eb_no_db(1e-6, &Modulation::Mqam(16)).unwrap();
+        let qam32: f64 = super::required_eb_no_db(1e-6, &Modulation::Mqam(32)).unwrap();
+        let qam64: f64 = super::required_eb_no_db(1e-6, &Modulation::Mqam(64)).unwrap();
+        let qam128: f64 = super::required_eb_no_db(1e-6, &Modulation::Mqam(128)).unwrap();
+        let qam256: f64 = super::required_eb_no_db(1e-6, &Modulation::Mqam(256)).unwrap();
+
+        assert!(qam16 < qam32 && qam32 < qam64);
+        assert!(qam64 < qam128 && qam128 < qam256);
+
+        // roughly midway, 32-QAM needs ~2 dB more than 16-QAM at 1e-6
+        assert!((qam32 - qam16 - 2.0).abs() < 0.5);
+    }
+
+    #[test]
+    fn apsk_without_ring_table() {
+        assert!(super::ber_from_db(10.0, &Modulation::Apsk(64)).is_nan());
+        assert!(super::ber_from_db(10.0, &Modulation::Apsk(8)).is_nan());
+        assert_eq!(None, super::required_eb_no_db(1e-6, &Modulation::Apsk(64)));
+    }
+
+    #[test]
+    fn rectangular_8qam() {
+        // the 4x2 grid sits between QPSK and 16-QAM and isn't treated as a cross constellation
+        let qpsk: f64 = super::required_eb_no_db(1e-6, &Modulation::Qpsk).unwrap();
+        let qam8: f64 = super::required_eb_no_db(1e-6, &Modulation::Mqam(8)).unwrap();
+        let qam16: f64 = super::required_eb_no_db(1e-6, &Modulation::Mqam(16)).unwrap();
+
+        assert!(qpsk < qam8 && qam8 < qam16);
+        assert_eq!((5.0 / 6.0) * super::q_function(10.0_f64.sqrt()), super::ber_mqam(10.0, 8));
+    }
+
+    #[test]
+    fn cross_qam_energy() {
+        // a 32-point cross on odd coordinates averages 20 (d = 2), the square formula
+        // would assume (M - 1) * d^2 / 6 = 20.7
+        let eb_no: f64 = 10.0;
+        let expected: f64 = (4.0 / 5.0) * (1.0 - 1.0 / 8.0) * super::q_function((15.0 * eb_no / 30.0).sqrt());
+
+        assert!((super::ber_mqam(eb_no, 32) / expected - 1.0).abs() < 1e-12);
+    }
+}
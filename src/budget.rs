@@ -1,45 +1,92 @@
-use crate::fspl::SlantRange;
+use core::fmt;
+use std::fmt::{Display, Formatter};
+
+use crate::ber;
+use crate::coding::CodedModulation;
+use crate::curve;
+use crate::energy;
+use crate::fspl;
+use crate::impairments::Impairments;
+use crate::modulation;
+use crate::modulation::Modulation;
+use crate::path_loss::PathLoss;
+use crate::pfd;
 use crate::phy::PhyRate;
 use crate::receiver::Receiver;
 use crate::transmitter::Transmitter;
+use crate::utils::format::si;
+
+// relative difference allowed between the link, transmitter and receiver bandwidths
+const BANDWIDTH_TOLERANCE: f64 = 1e-6;
 
-// elevation_angle and altitude could be moved to a struct
-// also could come from the position of the transmitter and receiver
-// and the radius of the body (lat/long/alt of the transmitter and receiver)
+// path_loss.distance can come from fspl::SlantRange (elevation angle and altitude)
+// or from the position of the transmitter and receiver
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone)]
 pub struct LinkBudget {
-    pub name: &'static str,
-    pub frequency: f64,
+    pub name: String,
     pub bandwidth: f64,
     pub transmitter: Transmitter,
     pub receiver: Receiver,
-    pub elevation_angle_degrees: f64,
-    pub altitude: f64,
-    pub rain_fade: f64,
+    pub path_loss: PathLoss,
+    pub frequency_dependent_loss: Option<f64>, // dB, e.g. rain fade or atmospheric absorption
 }
 
 impl LinkBudget {
-    pub fn fspl(&self) -> f64 {
-        let slant_range: f64 = SlantRange {
-            elevation_angle_degrees: self.elevation_angle_degrees,
-            altitude: self.altitude,
+    #[cfg(feature = "serde")]
+    pub fn from_toml_str(toml_str: &str) -> Result<LinkBudget, toml::de::Error> {
+        toml::from_str(toml_str)
+    }
+
+    pub fn validate_bandwidths(&self) -> Result<(), String> {
+        // the noise power comes from receiver.bandwidth, while C/No and Eb/No convert
+        // with the budget's bandwidth, so all three must agree for those to be right
+        for (name, bandwidth) in [
+            ("transmitter", self.transmitter.bandwidth),
+            ("receiver", self.receiver.bandwidth),
+        ] {
+            if (bandwidth - self.bandwidth).abs() > BANDWIDTH_TOLERANCE * self.bandwidth.abs() {
+                return Err(format!(
+                    "{} bandwidth {} does not match the link bandwidth {}",
+                    name,
+                    si(bandwidth, "Hz"),
+                    si(self.bandwidth, "Hz")
+                ));
+            }
         }
-        .calculate();
 
-        crate::fspl::calculate_free_space_path_loss(self.frequency, slant_range)
+        Ok(())
+    }
+
+    pub fn fspl(&self) -> f64 {
+        // free space path loss only, in dB
+        self.path_loss.calculate()
+    }
+
+    pub fn path_loss(&self) -> f64 {
+        // free space path loss plus any excess and frequency dependent loss, in dB
+        self.path_loss.total_loss() + self.frequency_dependent_loss.unwrap_or(0.0)
     }
 
     pub fn pin_at_receiver(&self) -> f64 {
-        let free_space_path_loss = self.fspl();
+        let path_loss = self.path_loss();
 
         // Assumes receiver input power is spread across the bandwidth
 
         // pin_at_receiver =
-        self.transmitter.output_power + self.transmitter.gain - free_space_path_loss - self.rain_fade + self.receiver.gain
+        self.transmitter.eirp_dbm() - path_loss + self.receiver.gain
     }
     pub fn snr(&self) -> f64 {
+        // pin_at_receiver includes the receiver gain, so the noise is referenced after
+        // the gain too and the gain cancels
         // returns value in dB
-        self.receiver.calculate_snr(self.pin_at_receiver())
+        self.receiver.calculate_snr_at_output(self.pin_at_receiver())
+    }
+
+    pub fn noise_at_receiver(&self) -> f64 {
+        // noise power in dBm at the same reference plane as pin_at_receiver
+        self.receiver.calculate_noise_power() + self.receiver.gain
     }
 
     pub fn snr_linear(&self) -> f64 {
@@ -47,6 +94,214 @@ impl LinkBudget {
         10.0_f64.powf(self.snr() / 10.0)
     }
 
+    pub fn c_over_n_plus_i_db(&self, interference_dbm: f64) -> f64 {
+        // noise and interference powers add in linear (mW), not in dB
+        // interference is referenced to the same plane as pin_at_receiver
+        let noise_mw: f64 = 10.0_f64.powf(self.noise_at_receiver() / 10.0);
+        let interference_mw: f64 = 10.0_f64.powf(interference_dbm / 10.0);
+
+        self.pin_at_receiver() - 10.0 * (noise_mw + interference_mw).log10()
+    }
+
+    pub fn c_over_no(&self) -> f64 {
+        // returns value in dB-Hz, using the budget bandwidth as the noise bandwidth
+        energy::snr_to_c_over_no(self.snr(), self.bandwidth)
+    }
+
+    pub fn eb_no_db(&self, modulation: &Modulation) -> f64 {
+        // Assumes the symbol rate equals the bandwidth, so Es/No == SNR
+        energy::es_over_no_to_eb_over_no(self.snr(), modulation)
+    }
+
+    pub fn es_no_db(&self, _modulation: &Modulation, symbol_rate: f64) -> f64 {
+        // energy per symbol, Es/No = C/No - 10*log10(Rs)
+        // takes the modulation like ec_no_db, though only the symbol rate sets Es/No
+        energy::c_over_no_to_es_over_no(self.c_over_no(), symbol_rate)
+    }
+
+    pub fn ec_no_db(&self, modulation: &Modulation, symbol_rate: f64) -> f64 {
+        // energy per coded (channel) bit, Es = k * Ec
+        // information bits carry Eb/No = Ec/No - 10*log10(code rate)
+        energy::es_over_no_to_eb_over_no(self.es_no_db(modulation, symbol_rate), modulation)
+    }
+
+    pub fn ber(&self, modulation: &Modulation) -> f64 {
+        ber::ber_from_db(self.eb_no_db(modulation), modulation)
+    }
+
+    pub fn link_margin_db(&self, modulation: &Modulation, target_ber: f64) -> Option<f64> {
+        // returns None for a target BER that no Eb/No can meet (or that is trivially met)
+        if target_ber <= 0.0 || target_ber >= 0.5 {
+            return None;
+        }
+
+        Some(self.eb_no_db(modulation) - ber::required_eb_no_db(target_ber, modulation)?)
+    }
+
+    pub fn composite_margin_db(
+        &self,
+        modulation: &Modulation,
+        target_ber: f64,
+        impairments: &Impairments,
+    ) -> Option<f64> {
+        // link_margin_db with every degradation source in one net margin
+        // C/I terms combine with the thermal SNR in linear, C/(N+I), before converting to
+        // Eb/No, and the dB penalties (implementation, phase noise, CFO) are then subtracted
+        if target_ber <= 0.0 || target_ber >= 0.5 {
+            return None;
+        }
+
+        let snr_db: f64 = match impairments.c_over_i_db() {
+            Some(c_over_i_db) => combine_c_over_i_and_c_over_n(c_over_i_db, self.snr()),
+            None => self.snr(),
+        };
+        let eb_no_db: f64 = energy::es_over_no_to_eb_over_no(snr_db, modulation) - impairments.total_penalty_db();
+
+        Some(eb_no_db - ber::required_eb_no_db(target_ber, modulation)?)
+    }
+
+    pub fn availability_percent(
+        &self,
+        modulation: &Modulation,
+        target_ber: f64,
+        fade_cdf: &[(f64, f64)],
+    ) -> Option<f64> {
+        // percent of time the link closes, given (fade depth dB, percent of time exceeded)
+        // points sorted by increasing fade, e.g. from rain::attenuation_for_availability
+        // The link fails whenever the fade exceeds the clear-sky margin. Percentages are
+        // interpolated linearly. None when the margin is below the first fade: the CDF
+        // only bounds the outage from below there, so any availability would be optimistic.
+        // Also None without a margin (no BER model or an invalid target) or an empty CDF
+        let (first, worst) = (fade_cdf.first()?, fade_cdf.last()?);
+
+        let margin: f64 = self.link_margin_db(modulation, target_ber)?;
+        if margin <= 0.0 {
+            // no clear-sky margin, the link never closes
+            return Some(0.0);
+        }
+
+        if margin > worst.0 {
+            Some(100.0)
+        } else if margin < first.0 {
+            None
+        } else {
+            Some(100.0 - curve::interp_y(fade_cdf, margin)?)
+        }
+    }
+
+    pub fn spread_link_margin_db(
+        &self,
+        modulation: &Modulation,
+        target_ber: f64,
+        chip_rate_hz: f64,
+        info_bit_rate_bps: f64,
+    ) -> Option<f64> {
+        // direct-sequence link: the SNR needed in the chip bandwidth is the required Eb/No
+        // less the processing gain, compared against the budget's SNR in that bandwidth
+        let required_snr_db: f64 = ber::required_eb_no_db(target_ber, modulation)?
+            - modulation::processing_gain_db(chip_rate_hz, info_bit_rate_bps);
+        let chip_snr_db: f64 = energy::c_over_no_to_snr(self.c_over_no(), chip_rate_hz);
+
+        Some(chip_snr_db - required_snr_db)
+    }
+
+    pub fn margin_vs_distance(
+        &self,
+        modulation: &Modulation,
+        target_ber: f64,
+        distances_m: &[f64],
+    ) -> Vec<(f64, Option<f64>)> {
+        // (distance, link margin) with the path loss recomputed at each distance,
+        // the margin crosses zero at the maximum range where the link closes
+        distances_m
+            .iter()
+            .map(|&distance| (distance, self.link_margin_at_distance_db(modulation, target_ber, distance)))
+            .collect()
+    }
+
+    pub fn margin_vs_elevation(
+        &self,
+        altitude_m: f64,
+        body_radius_m: f64,
+        modulation: &Modulation,
+        target_ber: f64,
+        elevation_degs: &[f64],
+    ) -> Vec<(f64, Option<f64>)> {
+        // (elevation, link margin) over a pass, the slant range sets the path loss distance
+        // Low elevations are long paths and the margin can go well negative
+        elevation_degs
+            .iter()
+            .map(|&elevation| {
+                let slant_range: f64 = fspl::calculate_slant_range(elevation, altitude_m, body_radius_m);
+                (elevation, self.link_margin_at_distance_db(modulation, target_ber, slant_range))
+            })
+            .collect()
+    }
+
+    fn link_margin_at_distance_db(&self, modulation: &Modulation, target_ber: f64, distance: f64) -> Option<f64> {
+        // only the free space loss depends on distance, so shift the margin by the
+        // change in FSPL rather than building a modified copy of the budget
+        let path_loss = PathLoss {
+            frequency: self.path_loss.frequency,
+            distance,
+            excess_loss_db: None,
+        };
+
+        Some(self.link_margin_db(modulation, target_ber)? + self.fspl() - path_loss.calculate())
+    }
+
+    pub fn required_uplink_eirp_dbm(&self, target_c_over_no_db_hz: f64) -> f64 {
+        // C/No moves dB for dB with EIRP at a fixed path loss and G/T,
+        // so close the gap between the current and target C/No
+        self.transmitter.eirp_dbm() + target_c_over_no_db_hz - self.c_over_no()
+    }
+
+    pub fn with_output_power(&self, output_power_dbm: f64) -> LinkBudget {
+        // copy of the budget with a new transmitter output power, e.g. after power control
+        let mut budget: LinkBudget = self.clone();
+        budget.transmitter.output_power = output_power_dbm;
+        budget
+    }
+
+    pub fn report(&self, modulation: &Modulation, target_ber: f64) -> LinkReport {
+        let eb_no_db: f64 = self.eb_no_db(modulation);
+        let required_eb_no_db: Option<f64> = ber::required_eb_no_db(target_ber, modulation);
+
+        LinkReport {
+            name: self.name.clone(),
+            modulation: *modulation,
+            target_ber,
+            eirp_dbm: self.transmitter.eirp_dbm(),
+            free_space_path_loss_db: self.fspl(),
+            // the PathLoss excess loss is reported with the budget level loss
+            frequency_dependent_loss_db: self.path_loss() - self.fspl(),
+            received_power_dbm: self.pin_at_receiver(),
+            noise_power_dbm: self.noise_at_receiver(),
+            snr_db: self.snr(),
+            c_over_no_db_hz: self.c_over_no(),
+            eb_no_db,
+            required_eb_no_db,
+            margin_db: required_eb_no_db.map(|required| eb_no_db - required),
+        }
+    }
+
+    pub fn eb_no_coded_db(&self, coded: &CodedModulation) -> f64 {
+        // Eb/No per information bit, the code rate spreads each symbol over fewer info bits
+        self.eb_no_db(&coded.modulation) - 10.0 * coded.fec.rate().log10()
+    }
+
+    pub fn ber_coded(&self, coded: &CodedModulation) -> f64 {
+        coded.ber_from_db(self.eb_no_coded_db(coded))
+    }
+
+    pub fn throughput_bps(&self, coded: &CodedModulation) -> f64 {
+        coded.throughput_bps(self.bandwidth)
+    }
+
+    pub fn link_margin_coded_db(&self, coded: &CodedModulation, target_ber: f64) -> Option<f64> {
+        coded.link_margin_db(self.eb_no_coded_db(coded), target_ber)
+    }
+
     pub fn phy_rate(&self) -> PhyRate {
         PhyRate {
             bandwidth: self.bandwidth,
@@ -55,3 +310,810 @@ impl LinkBudget {
     }
 }
 
+// Bent-pipe (transparent) transponder: an uplink and a downlink leg, which can sit at
+// different frequencies, e.g. 14 GHz up and 11 GHz down.
+// The transponder runs at fixed gain, so the downlink transmitter's output power is the
+// received uplink power plus transponder_gain_db (downlink.transmitter.output_power is ignored)
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone)]
+pub struct TransponderBudget {
+    pub uplink: LinkBudget,
+    pub downlink: LinkBudget,
+    pub transponder_gain_db: f64,
+    pub saturation_flux_density_dbw_m2: f64, // uplink PFD that drives the transponder to saturation
+}
+
+impl TransponderBudget {
+    pub fn downlink(&self) -> LinkBudget {
+        // downlink leg driven by the received uplink power
+        self.downlink
+            .with_output_power(self.uplink.pin_at_receiver() + self.transponder_gain_db)
+    }
+
+    pub fn uplink_pfd_dbw_per_m2(&self) -> f64 {
+        // flux density arriving at the satellite, spreading loss plus any uplink fade
+        // (excess and frequency dependent loss)
+        pfd::power_flux_density_dbw_per_m2(self.uplink.transmitter.eirp_dbw(), self.uplink.path_loss.distance)
+            - (self.uplink.path_loss() - self.uplink.fspl())
+    }
+
+    pub fn input_backoff_db(&self) -> f64 {
+        // dB below saturation, positive when the transponder is backed off
+        self.saturation_flux_density_dbw_m2 - self.uplink_pfd_dbw_per_m2()
+    }
+
+    pub fn overall_c_over_no(&self) -> f64 {
+        // end to end C/No in dB-Hz, both legs combined in linear
+        combine_link_c_over_no(self.uplink.c_over_no(), self.downlink().c_over_no())
+    }
+}
+
+// The whole ledger of a budget for one modulation and target BER
+// required_eb_no_db and margin_db are None when the target BER can't be met
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq)]
+pub struct LinkReport {
+    pub name: String,
+    pub modulation: Modulation,
+    pub target_ber: f64,
+    pub eirp_dbm: f64,
+    pub free_space_path_loss_db: f64,
+    pub frequency_dependent_loss_db: f64,
+    pub received_power_dbm: f64, // after the receive antenna gain
+    pub noise_power_dbm: f64,    // noise at the receiver output, after the receive gain (same plane as received_power_dbm)
+    pub snr_db: f64,
+    pub c_over_no_db_hz: f64,
+    pub eb_no_db: f64,
+    pub required_eb_no_db: Option<f64>,
+    pub margin_db: Option<f64>,
+}
+
+impl LinkReport {
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> String {
+        // floats are written shortest round-trip (and read back exactly with float_roundtrip)
+        serde_json::to_string_pretty(self).expect("a link report always serializes")
+    }
+}
+
+impl Display for LinkBudget {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        // label and value (with unit) columns, see LinkReport for the modem metrics
+        writeln!(f, "{}", self.name)?;
+        let rows: [(&str, String); 10] = [
+            ("Frequency", si(self.path_loss.frequency, "Hz")),
+            ("Distance", format!("{:.1} km", self.path_loss.distance / 1.0e3)),
+            ("Bandwidth", si(self.bandwidth, "Hz")),
+            ("EIRP", format!("{:.2} dBm", self.transmitter.eirp_dbm())),
+            ("Path Loss", format!("{:.2} dB", self.path_loss())),
+            ("Received Power", format!("{:.2} dBm", self.pin_at_receiver())),
+            ("Noise Power", format!("{:.2} dBm", self.noise_at_receiver())),
+            ("SNR", format!("{:.2} dB", self.snr())),
+            ("C/No", format!("{:.2} dB-Hz", self.c_over_no())),
+            ("PHY Rate", si(self.phy_rate().bps(), "bps")),
+        ];
+
+        for (label, value) in rows {
+            writeln!(f, "{:<26}{}", label, value)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for LinkReport {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        // label, value and unit columns
+        let optional = |value: Option<f64>| match value {
+            Some(value) => format!("{:.2}", value),
+            None => "-".to_string(),
+        };
+
+        writeln!(f, "{} ({}, BER {:e})", self.name, self.modulation, self.target_ber)?;
+        let rows: [(&str, String, &str); 10] = [
+            ("EIRP", format!("{:.2}", self.eirp_dbm), "dBm"),
+            ("Free Space Path Loss", format!("{:.2}", self.free_space_path_loss_db), "dB"),
+            ("Frequency Dependent Loss", format!("{:.2}", self.frequency_dependent_loss_db), "dB"),
+            ("Received Power", format!("{:.2}", self.received_power_dbm), "dBm"),
+            ("Noise Power", format!("{:.2}", self.noise_power_dbm), "dBm"),
+            ("SNR", format!("{:.2}", self.snr_db), "dB"),
+            ("C/No", format!("{:.2}", self.c_over_no_db_hz), "dB-Hz"),
+            ("Eb/No", format!("{:.2}", self.eb_no_db), "dB"),
+            ("Required Eb/No", optional(self.required_eb_no_db), "dB"),
+            ("Margin", optional(self.margin_db), "dB"),
+        ];
+
+        for (label, value, unit) in rows {
+            writeln!(f, "{:<26}{:>10} {}", label, value, unit)?;
+        }
+
+        Ok(())
+    }
+}
+
+pub fn combine_c_over_i_and_c_over_n(c_over_i_db: f64, c_over_n_db: f64) -> f64 {
+    // 1/(C/(N+I)) = 1/(C/I) + 1/(C/N), the ratios must be linear before summing
+    let c_over_i: f64 = 10.0_f64.powf(c_over_i_db / 10.0);
+    let c_over_n: f64 = 10.0_f64.powf(c_over_n_db / 10.0);
+
+    10.0 * (1.0 / (1.0 / c_over_i + 1.0 / c_over_n)).log10()
+}
+
+pub fn combine_link_c_over_no(uplink_c_no_db_hz: f64, downlink_c_no_db_hz: f64) -> f64 {
+    // bent-pipe total, 1/(C/No)_total = 1/(C/No)_up + 1/(C/No)_down
+    // combined in linear C/No (Hz), never by adding or averaging dB values
+    aggregate_end_to_end_c_over_no(&[uplink_c_no_db_hz, downlink_c_no_db_hz])
+}
+
+pub fn aggregate_end_to_end_c_over_no(legs: &[f64]) -> f64 {
+    // any number of transparent (non-regenerative) legs in series, e.g. ISL + feeder + user link
+    // 1/(C/No)_total = sum of 1/(C/No)_leg, each leg in dB-Hz
+    // Every leg's noise carries through, so the total is below the weakest leg.
+    // No legs add no noise, INFINITY
+    let reciprocal_sum: f64 = legs.iter().map(|c_no_db_hz| 10.0_f64.powf(-c_no_db_hz / 10.0)).sum();
+
+    -10.0 * reciprocal_sum.log10()
+}
+
+pub fn combine_link_c_over_no_with_intermod(
+    uplink_c_no_db_hz: f64,
+    downlink_c_no_db_hz: f64,
+    intermod_c_no_db_hz: f64,
+) -> f64 {
+    // transponder intermodulation is a third reciprocal term
+    let intermod: f64 = 10.0_f64.powf(intermod_c_no_db_hz / 10.0);
+    let links: f64 = 10.0_f64.powf(combine_link_c_over_no(uplink_c_no_db_hz, downlink_c_no_db_hz) / 10.0);
+
+    10.0 * (1.0 / (1.0 / links + 1.0 / intermod)).log10()
+}
+
+pub fn required_eirp_for_sensitivity_dbm(
+    path_loss_db: f64,
+    frequency_dependent_loss_db: f64,
+    rx_gain_db: f64,
+    sensitivity_dbm: f64,
+) -> f64 {
+    // minimum EIRP that lands exactly at the receiver sensitivity, e.g. from
+    // sensitivity::sensitivity_dbm, in the same terms as LinkBudget::pin_at_receiver:
+    // Pr = EIRP - path loss - frequency dependent loss + rx gain
+    // Losses are positive dB, the gain is in dBi
+    sensitivity_dbm + path_loss_db + frequency_dependent_loss_db - rx_gain_db
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LinkBudget, TransponderBudget};
+    use crate::coding;
+    use crate::fspl::SlantRange;
+    use crate::impairments::Impairments;
+    use crate::modulation::Modulation;
+    use crate::receiver::Receiver;
+    use crate::transmitter::Transmitter;
+    use crate::PathLoss;
+
+    fn ka_band_leo() -> LinkBudget {
+        // Ka-band LEO downlink from the README
+        let slant_range: f64 = SlantRange {
+            elevation_angle_degrees: 35.0,
+            altitude: 1.0e6,
+        }
+        .calculate();
+
+        LinkBudget {
+            name: "Ka-band LEO downlink".to_string(),
+            bandwidth: 100.0e6,
+            transmitter: Transmitter {
+                output_power: 40.0,
+                gain: 35.0,
+                bandwidth: 100.0e6,
+            },
+            receiver: Receiver {
+                gain: 40.0,
+                temperature: 290.0,
+                noise_figure: 3.0,
+                bandwidth: 100.0e6,
+                noise_components: None,
+            },
+            path_loss: PathLoss {
+                frequency: 28.0e9,
+                distance: slant_range,
+                excess_loss_db: None,
+            },
+            frequency_dependent_loss: Some(3.0),
+        }
+    }
+
+    #[test]
+    fn validate_bandwidths() {
+        assert_eq!(Ok(()), ka_band_leo().validate_bandwidths());
+    }
+
+    #[test]
+    fn validate_mismatched_bandwidths() {
+        let mut budget = ka_band_leo();
+        budget.receiver.bandwidth = 36.0e6;
+
+        assert_eq!(
+            Err("receiver bandwidth 36.0 MHz does not match the link bandwidth 100.0 MHz".to_string()),
+            budget.validate_bandwidths()
+        );
+
+        budget.receiver.bandwidth = 100.0e6;
+        budget.transmitter.bandwidth = 50.0e6;
+        assert!(budget.validate_bandwidths().unwrap_err().starts_with("transmitter bandwidth"));
+    }
+
+    #[test]
+    fn display() {
+        let budget = ka_band_leo();
+        let text: String = budget.to_string();
+
+        assert!(text.starts_with("Ka-band LEO downlink\n"));
+        assert!(text.contains("Frequency                 28.0 GHz\n"));
+        assert!(text.contains("Bandwidth                 100.0 MHz\n"));
+        assert!(text.contains(&format!("SNR                       {:.2} dB\n", budget.snr())));
+        assert!(text.contains("PHY Rate"));
+    }
+
+    #[test]
+    fn path_loss() {
+        let budget = ka_band_leo();
+
+        assert!(budget.path_loss() > 170.0);
+        assert_eq!(185.2036631301259, budget.fspl());
+        assert_eq!(188.2036631301259, budget.path_loss());
+    }
+
+    #[test]
+    fn no_frequency_dependent_loss() {
+        let mut budget = ka_band_leo();
+        budget.frequency_dependent_loss = None;
+
+        assert_eq!(budget.fspl(), budget.path_loss());
+    }
+
+    #[test]
+    fn c_over_no() {
+        let budget = ka_band_leo();
+
+        let expected: f64 = budget.snr() + 10.0 * budget.bandwidth.log10();
+        assert_eq!(expected, budget.c_over_no());
+    }
+
+    #[test]
+    fn eb_no_db() {
+        let budget = ka_band_leo();
+
+        let expected: f64 = budget.snr() - 10.0 * 2.0_f64.log10();
+        assert_eq!(expected, budget.eb_no_db(&Modulation::Qpsk));
+        assert_eq!(budget.snr(), budget.eb_no_db(&Modulation::Bpsk));
+    }
+
+    #[test]
+    fn snr_is_invariant_to_receiver_gain() {
+        let low_gain = ka_band_leo();
+        let mut high_gain = ka_band_leo();
+        high_gain.receiver.gain += 20.0;
+
+        assert_eq!(low_gain.snr(), high_gain.snr());
+        assert_eq!(low_gain.c_over_no(), high_gain.c_over_no());
+        assert!(high_gain.pin_at_receiver() > low_gain.pin_at_receiver());
+    }
+
+    #[test]
+    fn es_no_and_ec_no() {
+        let budget = ka_band_leo();
+
+        // symbol rate equal to the bandwidth: Es/No == SNR and Ec/No is the uncoded Eb/No
+        assert!((budget.es_no_db(&Modulation::Qpsk, budget.bandwidth) - budget.snr()).abs() < 1e-9);
+        assert_eq!(
+            budget.es_no_db(&Modulation::Qpsk, 80.0e6),
+            budget.es_no_db(&Modulation::Mqam(64), 80.0e6)
+        );
+        assert!((budget.ec_no_db(&Modulation::Qpsk, budget.bandwidth) - budget.eb_no_db(&Modulation::Qpsk)).abs() < 1e-9);
+
+        // rate 1/2 QPSK at 80 Msps carries 80 Mbps of information
+        let ec_no: f64 = budget.ec_no_db(&Modulation::Qpsk, 80.0e6);
+        let eb_no: f64 = crate::energy::c_over_no_to_eb_over_no(budget.c_over_no(), 80.0e6);
+
+        assert!((ec_no - 10.0 * 0.5_f64.log10() - eb_no).abs() < 1e-9);
+    }
+
+    #[test]
+    fn ber() {
+        // close enough in for the link to close
+        let mut budget = ka_band_leo();
+        budget.path_loss.distance = 1.0e4;
+
+        let ber: f64 = budget.ber(&Modulation::Qpsk);
+        assert!(ber > 0.0 && ber <= 0.5);
+        assert!(budget.ber(&Modulation::Qpsk) < budget.ber(&Modulation::Mqam(64)));
+    }
+
+    #[test]
+    fn link_margin_uncoded() {
+        let mut budget = ka_band_leo();
+        assert!(budget.link_margin_db(&Modulation::Qpsk, 1e-5).unwrap() < 0.0);
+
+        budget.path_loss.distance = 1.0e4;
+        let margin: f64 = budget.link_margin_db(&Modulation::Qpsk, 1e-5).unwrap();
+        assert!(margin > 0.0);
+        assert!(budget.link_margin_db(&Modulation::Qpsk, 0.0).is_none());
+    }
+
+    #[test]
+    fn availability_at_the_one_tenth_percent_fade() {
+        let mut budget = ka_band_leo();
+        budget.path_loss.distance = 1.0e4;
+        let margin: f64 = budget.link_margin_db(&Modulation::Qpsk, 1e-5).unwrap();
+
+        // the margin exactly covers the fade exceeded 0.1% of the time
+        let fade_cdf: [(f64, f64); 3] = [(margin - 4.0, 1.0), (margin, 0.1), (margin + 6.0, 0.01)];
+        let availability: f64 = budget.availability_percent(&Modulation::Qpsk, 1e-5, &fade_cdf).unwrap();
+
+        assert!((availability - 99.9).abs() < 1e-9);
+    }
+
+    #[test]
+    fn availability_below_the_first_fade() {
+        let mut budget = ka_band_leo();
+        budget.path_loss.distance = 1.0e4;
+        let margin: f64 = budget.link_margin_db(&Modulation::Qpsk, 1e-5).unwrap();
+
+        // the table starts past the margin, the outage is at least 1% but could be more
+        let fade_cdf: [(f64, f64); 2] = [(margin + 1.0, 1.0), (margin + 6.0, 0.01)];
+        assert_eq!(None, budget.availability_percent(&Modulation::Qpsk, 1e-5, &fade_cdf));
+
+        // exactly on the first point is still known
+        let fade_cdf: [(f64, f64); 2] = [(margin, 1.0), (margin + 6.0, 0.01)];
+        let availability: f64 = budget.availability_percent(&Modulation::Qpsk, 1e-5, &fade_cdf).unwrap();
+        assert!((availability - 99.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn availability_beyond_the_worst_fade() {
+        let mut budget = ka_band_leo();
+        budget.path_loss.distance = 1.0e4;
+        let fade_cdf: [(f64, f64); 3] = [(1.0, 1.0), (3.0, 0.1), (8.0, 0.01)];
+
+        assert_eq!(Some(100.0), budget.availability_percent(&Modulation::Qpsk, 1e-5, &fade_cdf));
+
+        // no clear-sky margin, the link never closes
+        budget.path_loss.distance = 1.0e7;
+        assert_eq!(Some(0.0), budget.availability_percent(&Modulation::Qpsk, 1e-5, &fade_cdf));
+    }
+
+    #[test]
+    fn availability_at_the_worst_fade() {
+        let mut budget = ka_band_leo();
+        budget.path_loss.distance = 1.0e4;
+        let margin: f64 = budget.link_margin_db(&Modulation::Qpsk, 1e-5).unwrap();
+
+        // a margin exactly at the last point still fails for the 0.01% of time it's exceeded
+        let fade_cdf: [(f64, f64); 2] = [(margin - 6.0, 1.0), (margin, 0.01)];
+        let availability: f64 = budget.availability_percent(&Modulation::Qpsk, 1e-5, &fade_cdf).unwrap();
+
+        assert!((availability - 99.99).abs() < 1e-9);
+    }
+
+    #[test]
+    fn availability_without_a_margin_or_cdf() {
+        let mut budget = ka_band_leo();
+        budget.path_loss.distance = 1.0e4;
+        let fade_cdf: [(f64, f64); 2] = [(1.0, 1.0), (8.0, 0.01)];
+
+        // no BER model for 64APSK and an invalid target BER are unknown, not an outage
+        assert_eq!(None, budget.availability_percent(&Modulation::Apsk(64), 1e-5, &fade_cdf));
+        assert_eq!(None, budget.availability_percent(&Modulation::Qpsk, 2.0, &fade_cdf));
+
+        // nothing to compare the margin against
+        assert_eq!(None, budget.availability_percent(&Modulation::Qpsk, 1e-5, &[]));
+    }
+
+    #[test]
+    fn spread_link_margin_db() {
+        // the slant range doesn't close at 100 Mbps, spreading 1 Mbps over the same
+        // 100 Mcps recovers the 20 dB of processing gain
+        let budget = ka_band_leo();
+        let unspread: f64 = budget.link_margin_db(&Modulation::Bpsk, 1e-5).unwrap();
+        let spread: f64 = budget
+            .spread_link_margin_db(&Modulation::Bpsk, 1e-5, 100.0e6, 1.0e6)
+            .unwrap();
+
+        assert!(unspread < 0.0);
+        assert!((spread - unspread - 20.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn margin_vs_distance() {
+        let budget = ka_band_leo();
+        let distances: Vec<f64> = (0..=60).map(|i| 1.0e3 * 10.0_f64.powf(i as f64 / 10.0)).collect();
+
+        let margins = budget.margin_vs_distance(&Modulation::Qpsk, 1e-5, &distances);
+        assert_eq!(distances.len(), margins.len());
+
+        // 6 dB per doubling, so strictly decreasing
+        for pair in margins.windows(2) {
+            assert!(pair[1].1.unwrap() < pair[0].1.unwrap());
+        }
+
+        // closes up close, fails far out
+        assert!(margins[0].1.unwrap() > 0.0);
+        assert!(margins[margins.len() - 1].1.unwrap() < 0.0);
+
+        // the budget's own distance gives the plain link margin
+        let own = budget.margin_vs_distance(&Modulation::Qpsk, 1e-5, &[budget.path_loss.distance]);
+        assert!((budget.link_margin_db(&Modulation::Qpsk, 1e-5).unwrap() - own[0].1.unwrap()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn margin_vs_elevation() {
+        let budget = ka_band_leo();
+        let elevations: [f64; 5] = [0.0, 10.0, 35.0, 60.0, 90.0];
+
+        let margins = budget.margin_vs_elevation(
+            1.0e6,
+            crate::constants::RADIUS_OF_EARTH,
+            &Modulation::Qpsk,
+            1e-5,
+            &elevations,
+        );
+
+        let horizon: f64 = margins[0].1.unwrap();
+        let zenith: f64 = margins[4].1.unwrap();
+        assert!(zenith > horizon);
+
+        // 35 degrees is the budget's own geometry
+        let own: f64 = budget.link_margin_db(&Modulation::Qpsk, 1e-5).unwrap();
+        assert!((margins[2].1.unwrap() - own).abs() < 1e-9);
+    }
+
+    #[test]
+    fn uplink_power_control() {
+        let budget = ka_band_leo();
+        let target: f64 = budget.c_over_no() + 4.5;
+
+        let eirp: f64 = budget.required_uplink_eirp_dbm(target);
+        let adjusted = budget.with_output_power(eirp - budget.transmitter.gain);
+
+        assert!((adjusted.c_over_no() - target).abs() < 1e-9);
+        assert!((adjusted.transmitter.eirp_dbm() - eirp).abs() < 1e-12);
+
+        // the original is untouched
+        assert_eq!(40.0, budget.transmitter.output_power);
+    }
+
+    #[test]
+    fn report() {
+        let budget = ka_band_leo();
+        let report = budget.report(&Modulation::Qpsk, 1e-5);
+
+        assert_eq!(budget.snr(), report.snr_db);
+        assert_eq!(budget.c_over_no(), report.c_over_no_db_hz);
+        assert_eq!(budget.link_margin_db(&Modulation::Qpsk, 1e-5), report.margin_db);
+        assert_eq!(
+            report.eirp_dbm - report.free_space_path_loss_db - report.frequency_dependent_loss_db + budget.receiver.gain,
+            report.received_power_dbm
+        );
+
+        let table: String = report.to_string();
+        assert!(table.starts_with("Ka-band LEO downlink (QPSK, BER 1e-5)\n"));
+        assert!(table.contains(&format!("SNR                       {:>10} dB\n", format!("{:.2}", report.snr_db))));
+    }
+
+    #[test]
+    fn report_unreachable_target() {
+        let report = ka_band_leo().report(&Modulation::Qpsk, 0.6);
+
+        assert_eq!(None, report.margin_db);
+        assert!(report.to_string().contains("Margin                             - dB\n"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn report_to_json() {
+        let budget = ka_band_leo();
+        let report = budget.report(&Modulation::Mqam(16), 1e-6);
+
+        let json: String = report.to_json();
+        assert!(json.contains("\"modulation\": \"16-QAM\""));
+
+        let parsed: super::LinkReport = serde_json::from_str(&json).unwrap();
+        assert_eq!(report, parsed);
+        assert_eq!(budget.snr(), parsed.snr_db);
+        assert_eq!(budget.link_margin_db(&Modulation::Mqam(16), 1e-6), parsed.margin_db);
+    }
+
+    #[test]
+    fn coded_performance() {
+        let mut budget = ka_band_leo();
+        budget.bandwidth = 36.0e6;
+        let coded = coding::dvbs2_qpsk_r34();
+
+        assert!(budget.ber_coded(&coded) <= budget.ber(&Modulation::Qpsk));
+        assert_eq!(54.0e6, budget.throughput_bps(&coded));
+
+        let uncoded_margin: f64 = budget.link_margin_db(&Modulation::Qpsk, 1e-5).unwrap();
+        assert!(budget.link_margin_coded_db(&coded, 1e-5).unwrap() > uncoded_margin);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn from_toml_str() {
+        let budget = LinkBudget::from_toml_str(include_str!("../files/example.toml")).unwrap();
+
+        assert_eq!("Ka-band LEO downlink", budget.name);
+        assert_eq!(28.0e9, budget.path_loss.frequency);
+        assert_eq!(Some(3.0), budget.frequency_dependent_loss);
+        assert_eq!(ka_band_leo().snr(), budget.snr());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn from_toml_str_missing_field() {
+        assert!(LinkBudget::from_toml_str("name = \"incomplete\"").is_err());
+    }
+
+    #[test]
+    fn c_over_n_plus_i_negligible_interference() {
+        let budget = ka_band_leo();
+
+        // interference 60 dB under the noise
+        let noise_power: f64 = budget.noise_at_receiver();
+        let c_over_n_plus_i: f64 = budget.c_over_n_plus_i_db(noise_power - 60.0);
+
+        assert!((budget.snr() - c_over_n_plus_i).abs() < 1e-5);
+    }
+
+    #[test]
+    fn c_over_n_plus_i_dominant_interference() {
+        let budget = ka_band_leo();
+
+        // interference 40 dB over the noise
+        let interference: f64 = budget.noise_at_receiver() + 40.0;
+        let c_over_i: f64 = budget.pin_at_receiver() - interference;
+
+        assert!((budget.c_over_n_plus_i_db(interference) - c_over_i).abs() < 1e-3);
+    }
+
+    #[test]
+    fn required_eirp_for_sensitivity_dbm() {
+        let budget = ka_band_leo();
+        let sensitivity: f64 = crate::sensitivity::sensitivity_dbm(100.0e6, 3.0, 10.0);
+
+        let eirp: f64 =
+            super::required_eirp_for_sensitivity_dbm(budget.fspl(), 3.0, budget.receiver.gain, sensitivity);
+        let adjusted = budget.with_output_power(eirp - budget.transmitter.gain);
+
+        assert!((adjusted.pin_at_receiver() - sensitivity).abs() < 1e-9);
+    }
+
+    #[test]
+    fn combine_equal_c_over_i_and_c_over_n() {
+        // equal contributions double the impairment, 3 dB worse
+        let combined: f64 = super::combine_c_over_i_and_c_over_n(20.0, 20.0);
+
+        assert!((combined - (20.0 - 10.0 * 2.0_f64.log10())).abs() < 1e-12);
+    }
+
+    #[test]
+    fn combine_dominant_c_over_i() {
+        let combined: f64 = super::combine_c_over_i_and_c_over_n(10.0, 40.0);
+
+        assert!(combined < 10.0);
+        assert!(10.0 - combined < 0.01);
+    }
+
+    #[test]
+    fn combine_link_c_over_no_below_weaker_leg() {
+        let combined: f64 = super::combine_link_c_over_no(90.0, 85.0);
+
+        assert!(combined < 85.0);
+        assert_eq!(83.80668951933906, combined);
+    }
+
+    #[test]
+    fn combine_link_c_over_no_dominant_leg() {
+        // a 30 dB stronger uplink leaves the downlink in charge
+        let combined: f64 = super::combine_link_c_over_no(110.0, 80.0);
+
+        assert!(combined < 80.0);
+        assert!(80.0 - combined < 0.01);
+    }
+
+    #[test]
+    fn combine_link_c_over_no_with_intermod() {
+        let without: f64 = super::combine_link_c_over_no(90.0, 85.0);
+        let with: f64 = super::combine_link_c_over_no_with_intermod(90.0, 85.0, 88.0);
+
+        assert!(with < without);
+        assert!(with < 85.0);
+    }
+
+    fn ku_band_geo_leg(name: &str, frequency: f64, output_power: f64) -> LinkBudget {
+        LinkBudget {
+            name: name.to_string(),
+            bandwidth: 36.0e6,
+            transmitter: Transmitter {
+                output_power,
+                gain: 50.0,
+                bandwidth: 36.0e6,
+            },
+            receiver: Receiver {
+                gain: 30.0,
+                temperature: 290.0,
+                noise_figure: 2.0,
+                bandwidth: 36.0e6,
+                noise_components: None,
+            },
+            path_loss: PathLoss {
+                frequency,
+                distance: 38.0e6,
+                excess_loss_db: None,
+            },
+            frequency_dependent_loss: None,
+        }
+    }
+
+    fn ku_band_transponder() -> TransponderBudget {
+        // 14 GHz up, 11 GHz down
+        TransponderBudget {
+            uplink: ku_band_geo_leg("Ku-band uplink", 14.0e9, 55.0),
+            downlink: ku_band_geo_leg("Ku-band downlink", 11.0e9, 0.0),
+            transponder_gain_db: 110.0,
+            saturation_flux_density_dbw_m2: -85.0,
+        }
+    }
+
+    #[test]
+    fn transponder_drives_the_downlink() {
+        let transponder = ku_band_transponder();
+        let downlink = transponder.downlink();
+
+        assert_eq!(
+            transponder.uplink.pin_at_receiver() + 110.0,
+            downlink.transmitter.output_power
+        );
+        assert_eq!(11.0e9, downlink.path_loss.frequency);
+    }
+
+    #[test]
+    fn overall_c_over_no_below_each_leg() {
+        let transponder = ku_band_transponder();
+        let overall: f64 = transponder.overall_c_over_no();
+
+        assert!(overall < transponder.uplink.c_over_no());
+        assert!(overall < transponder.downlink().c_over_no());
+    }
+
+    #[test]
+    fn uplink_fade() {
+        let clear_sky = ku_band_transponder();
+        let mut faded = ku_band_transponder();
+        faded.uplink.frequency_dependent_loss = Some(4.0);
+
+        // the fade backs the transponder off and, at fixed gain, carries through to the downlink
+        assert!((faded.input_backoff_db() - clear_sky.input_backoff_db() - 4.0).abs() < 1e-9);
+        assert!((clear_sky.downlink().c_over_no() - faded.downlink().c_over_no() - 4.0).abs() < 1e-9);
+        assert!(faded.overall_c_over_no() < clear_sky.overall_c_over_no() - 3.9);
+    }
+
+    #[test]
+    fn input_backoff_db() {
+        let transponder = ku_band_transponder();
+
+        // 75 dBW EIRP spread over 38,000 km is ~ -87.6 dBW/m^2, ~2.6 dB below the -85 SFD
+        let pfd: f64 = transponder.uplink_pfd_dbw_per_m2();
+        assert!((pfd + 87.59).abs() < 0.01);
+        assert!((transponder.input_backoff_db() - (-85.0 - pfd)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn composite_margin_ideal_matches_link_margin() {
+        let mut budget: LinkBudget = ka_band_leo();
+        budget.path_loss.distance = 1.0e4;
+
+        let ideal: Option<f64> = budget.composite_margin_db(&Modulation::Qpsk, 1e-6, &Impairments::default());
+
+        assert_eq!(budget.link_margin_db(&Modulation::Qpsk, 1e-6), ideal);
+    }
+
+    #[test]
+    fn composite_margin_impairments_reduce_margin() {
+        let mut budget: LinkBudget = ka_band_leo();
+        budget.path_loss.distance = 1.0e4;
+        let ideal: f64 = budget
+            .composite_margin_db(&Modulation::Qpsk, 1e-6, &Impairments::default())
+            .unwrap();
+
+        let cases: [Impairments; 6] = [
+            Impairments {
+                implementation_loss_db: 1.5,
+                ..Impairments::default()
+            },
+            Impairments {
+                phase_noise_penalty_db: 0.2,
+                ..Impairments::default()
+            },
+            Impairments {
+                cfo_penalty_db: 0.05,
+                ..Impairments::default()
+            },
+            Impairments {
+                aci_c_over_i_db: Some(25.0),
+                ..Impairments::default()
+            },
+            Impairments {
+                intermod_c_over_i_db: Some(18.0),
+                ..Impairments::default()
+            },
+            Impairments {
+                cochannel_c_over_i_db: Some(30.0),
+                ..Impairments::default()
+            },
+        ];
+
+        for impairments in cases {
+            let margin: f64 = budget.composite_margin_db(&Modulation::Qpsk, 1e-6, &impairments).unwrap();
+
+            assert!(margin < ideal);
+        }
+
+        // a penalty comes straight off the margin
+        let margin: f64 = budget
+            .composite_margin_db(
+                &Modulation::Qpsk,
+                1e-6,
+                &Impairments {
+                    implementation_loss_db: 1.5,
+                    ..Impairments::default()
+                },
+            )
+            .unwrap();
+        assert!((ideal - margin - 1.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn composite_margin_interference_limited() {
+        // with strong C/N the margin is capped by C/I, no matter how close the link
+        let mut budget: LinkBudget = ka_band_leo();
+        budget.path_loss.distance = 1.0e2;
+        let impairments = Impairments {
+            aci_c_over_i_db: Some(12.0),
+            ..Impairments::default()
+        };
+
+        let margin: f64 = budget.composite_margin_db(&Modulation::Qpsk, 1e-6, &impairments).unwrap();
+        let required: f64 = crate::ber::required_eb_no_db(1e-6, &Modulation::Qpsk).unwrap();
+
+        assert!(margin < 12.0 - 3.0103 - required + 0.01);
+    }
+
+    #[test]
+    fn path_loss_includes_excess_loss() {
+        let mut budget: LinkBudget = ka_band_leo();
+        let without: f64 = budget.path_loss();
+
+        budget.path_loss.excess_loss_db = Some(2.0);
+
+        assert!((budget.path_loss() - without - 2.0).abs() < 1e-12);
+        assert_eq!(without - 3.0, budget.fspl());
+        assert!((budget.report(&Modulation::Qpsk, 1e-6).frequency_dependent_loss_db - 5.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn aggregate_three_equal_legs() {
+        let total: f64 = super::aggregate_end_to_end_c_over_no(&[80.0, 80.0, 80.0]);
+
+        assert!((total - (80.0 - 10.0 * 3.0_f64.log10())).abs() < 1e-9);
+    }
+
+    #[test]
+    fn aggregate_matches_two_leg_combine() {
+        let two_leg: f64 = super::combine_link_c_over_no(85.0, 78.0);
+
+        assert!((super::aggregate_end_to_end_c_over_no(&[85.0, 78.0]) - two_leg).abs() < 1e-12);
+        assert_eq!(72.0, super::aggregate_end_to_end_c_over_no(&[72.0]));
+        assert_eq!(f64::INFINITY, super::aggregate_end_to_end_c_over_no(&[]));
+    }
+}
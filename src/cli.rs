@@ -0,0 +1,157 @@
+use std::fs;
+use std::path::Path;
+
+use crate::budget::LinkBudget;
+
+pub struct Command {
+    pub input_path: String,
+    pub output_path: String,
+}
+
+impl Command {
+    pub fn from_args(args: &[String]) -> Result<Command, String> {
+        // linkbudget <config.toml> [output.html]
+        let input_path: &String = args
+            .first()
+            .ok_or("usage: linkbudget <config.toml> [output.html]")?;
+
+        let output_path: String = match args.get(1) {
+            Some(output) => output_path(output),
+            // default to the input's file name next to the current directory
+            None => {
+                let stem = Path::new(input_path)
+                    .file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .unwrap_or("linkbudget");
+                output_path(stem)
+            }
+        };
+
+        Ok(Command {
+            input_path: input_path.to_string(),
+            output_path,
+        })
+    }
+
+    pub fn run(&self) -> Result<LinkBudget, String> {
+        let extension = Path::new(&self.input_path)
+            .extension()
+            .and_then(|extension| extension.to_str());
+
+        if extension != Some("toml") {
+            return Err(format!("{}: unsupported file type, expected .toml", self.input_path));
+        }
+
+        let contents: String = fs::read_to_string(&self.input_path)
+            .map_err(|error| format!("{}: {}", self.input_path, error))?;
+
+        let budget: LinkBudget = LinkBudget::from_toml_str(&contents)
+            .map_err(|error| format!("{}: invalid link budget: {}", self.input_path, error))?;
+        budget
+            .validate_bandwidths()
+            .map_err(|error| format!("{}: invalid link budget: {}", self.input_path, error))?;
+
+        crate::plot::generate_html_summary(&budget, None, &self.output_path)
+            .map_err(|error| format!("{}: {}", self.output_path, error))?;
+
+        Ok(budget)
+    }
+}
+
+fn output_path(output: &str) -> String {
+    // bare filename -> ./filename.html, anything with a directory is kept as is
+    let mut path: String = output.to_string();
+    if !path.ends_with(".html") {
+        path.push_str(".html");
+    }
+
+    if Path::new(&path).parent() == Some(Path::new("")) {
+        format!("./{}", path)
+    } else {
+        path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Command;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn output_path() {
+        assert_eq!("./example.html", super::output_path("example"));
+        assert_eq!("./example.html", super::output_path("example.html"));
+        assert_eq!("out/example.html", super::output_path("out/example.html"));
+        assert_eq!("/tmp/example.html", super::output_path("/tmp/example"));
+    }
+
+    #[test]
+    fn from_args() {
+        let command = Command::from_args(&args(&["files/example.toml"])).unwrap();
+        assert_eq!("files/example.toml", command.input_path);
+        assert_eq!("./example.html", command.output_path);
+
+        let command = Command::from_args(&args(&["files/example.toml", "summary"])).unwrap();
+        assert_eq!("./summary.html", command.output_path);
+
+        assert!(Command::from_args(&[]).is_err());
+    }
+
+    #[test]
+    fn run_example() {
+        let output = crate::utils::temp_path("cli_example.html");
+        let command = Command {
+            input_path: "files/example.toml".to_string(),
+            output_path: output.to_str().unwrap().to_string(),
+        };
+
+        let budget = command.run().unwrap();
+        let html: String = std::fs::read_to_string(&command.output_path).unwrap();
+
+        assert!(html.contains(&budget.name));
+        assert!(html.contains("Ka-band LEO downlink"));
+        assert!(!html.contains("Test Link"));
+    }
+
+    #[test]
+    fn run_missing_field() {
+        let input = crate::utils::temp_path("cli_incomplete.toml");
+        std::fs::write(&input, "name = \"incomplete\"\nbandwidth = 1.0e6\n").unwrap();
+
+        let command = Command {
+            input_path: input.to_str().unwrap().to_string(),
+            output_path: crate::utils::temp_path("cli_incomplete.html").to_str().unwrap().to_string(),
+        };
+
+        let error: String = command.run().err().unwrap();
+        assert!(error.contains("missing field"));
+    }
+
+    #[test]
+    fn run_mismatched_bandwidth() {
+        let example: String = std::fs::read_to_string("files/example.toml").unwrap();
+        let input = crate::utils::temp_path("cli_mismatched.toml");
+        std::fs::write(&input, example.replacen("bandwidth = 100.0e6  # Hz", "bandwidth = 36.0e6  # Hz", 1)).unwrap();
+
+        let command = Command {
+            input_path: input.to_str().unwrap().to_string(),
+            output_path: crate::utils::temp_path("cli_mismatched.html").to_str().unwrap().to_string(),
+        };
+
+        let error: String = command.run().err().unwrap();
+        assert!(error.contains("does not match the link bandwidth"));
+    }
+
+    #[test]
+    fn run_unsupported_type() {
+        let command = Command {
+            input_path: "files/example.json".to_string(),
+            output_path: crate::utils::temp_path("cli_unsupported.html").to_str().unwrap().to_string(),
+        };
+
+        assert!(command.run().err().unwrap().contains("unsupported file type"));
+    }
+}
@@ -0,0 +1,999 @@
+use core::fmt;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::ber;
+use crate::energy;
+use crate::modulation::Modulation;
+use crate::phy;
+
+// Nominal coding gains (dB) versus uncoded BPSK/QPSK at a BER of 1e-5, AWGN channel.
+// Each table is (code rate, coding gain) sorted by rate, and is linearly interpolated.
+const CONVOLUTIONAL_GAIN_TABLE: [(f64, f64); 4] = [(0.5, 5.0), (2.0 / 3.0, 4.5), (0.75, 4.0), (0.875, 3.0)];
+const TURBO_GAIN_TABLE: [(f64, f64); 3] = [(1.0 / 3.0, 8.6), (0.5, 8.0), (0.75, 6.5)];
+const LDPC_GAIN_TABLE: [(f64, f64); 4] = [(0.25, 9.5), (0.5, 8.6), (0.75, 7.3), (0.9, 5.7)];
+
+// BER the nominal gains are quoted at
+const NOMINAL_GAIN_BER: f64 = 1e-5;
+
+// Extra coding gain (dB) per decade of BER below NOMINAL_GAIN_BER. The uncoded curve needs
+// ~0.8 dB more Eb/No per decade around 1e-6..1e-8 while a turbo or LDPC waterfall barely
+// moves, so nearly all of that is gain. Viterbi-decoded convolutional codes roll off more
+// slowly and keep about half of it.
+const CONVOLUTIONAL_GAIN_SLOPE_DB_PER_DECADE: f64 = 0.35;
+const CAPACITY_APPROACHING_GAIN_SLOPE_DB_PER_DECADE: f64 = 0.7;
+
+// DVB-S2 quasi-error-free (PER 1e-7) Es/No thresholds (dB), normal 64800 bit frames in AWGN,
+// ETSI EN 302 307 table 13. Each table is (LDPC code rate, Es/No) for one constellation.
+const DVBS2_QPSK_ES_NO_TABLE: [(f64, f64); 11] = [
+    (1.0 / 4.0, -2.35),
+    (1.0 / 3.0, -1.24),
+    (2.0 / 5.0, -0.30),
+    (1.0 / 2.0, 1.00),
+    (3.0 / 5.0, 2.23),
+    (2.0 / 3.0, 3.10),
+    (3.0 / 4.0, 4.03),
+    (4.0 / 5.0, 4.68),
+    (5.0 / 6.0, 5.18),
+    (8.0 / 9.0, 6.20),
+    (9.0 / 10.0, 6.42),
+];
+const DVBS2_8PSK_ES_NO_TABLE: [(f64, f64); 6] = [
+    (3.0 / 5.0, 5.50),
+    (2.0 / 3.0, 6.62),
+    (3.0 / 4.0, 7.91),
+    (5.0 / 6.0, 9.35),
+    (8.0 / 9.0, 10.69),
+    (9.0 / 10.0, 10.98),
+];
+const DVBS2_16APSK_ES_NO_TABLE: [(f64, f64); 6] = [
+    (2.0 / 3.0, 8.97),
+    (3.0 / 4.0, 10.21),
+    (4.0 / 5.0, 11.03),
+    (5.0 / 6.0, 11.61),
+    (8.0 / 9.0, 12.89),
+    (9.0 / 10.0, 13.13),
+];
+const DVBS2_32APSK_ES_NO_TABLE: [(f64, f64); 5] = [
+    (3.0 / 4.0, 12.73),
+    (4.0 / 5.0, 13.64),
+    (5.0 / 6.0, 14.28),
+    (8.0 / 9.0, 15.69),
+    (9.0 / 10.0, 16.05),
+];
+
+// bisection settings for CodedModulation::required_eb_no_db, the bracket reaches further
+// down than the uncoded search to leave room for the coding gain
+const CODED_EB_NO_LOW_DB: f64 = -20.0;
+const CODED_EB_NO_HIGH_DB: f64 = 50.0;
+const CODED_EB_NO_TOLERANCE: f64 = 1e-6;
+const CODED_EB_NO_MAX_ITERATIONS: usize = 100;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum FecCode {
+    Uncoded,
+    Convolutional { rate: f64 },
+    Turbo { rate: f64 },
+    Ldpc { rate: f64 },
+    Custom { rate: f64, coding_gain_db: f64 },
+    // custom code whose BER flattens at floor_ber instead of falling indefinitely,
+    // the error floor of real turbo and LDPC decoders
+    WithFloor { rate: f64, coding_gain_db: f64, floor_ber: f64 },
+    // outer code wrapped around an inner code, e.g. Reed-Solomon + convolutional
+    Concatenated { outer: Box<FecCode>, inner: Box<FecCode> },
+    // puncture_rate is the fraction of the base code's output bits that are transmitted (0, 1]
+    // Not checked when built directly (0 gives an infinite rate), see FecCode::try_punctured
+    Punctured { base: Box<FecCode>, puncture_rate: f64 },
+}
+
+impl FecCode {
+    pub fn try_punctured(base: FecCode, puncture_rate: f64) -> Result<FecCode, FecCodeError> {
+        // puncture_rate must be in (0, 1], 1 transmits every bit of the base code
+        if puncture_rate.is_nan() || puncture_rate <= 0.0 || puncture_rate > 1.0 {
+            return Err(FecCodeError::InvalidPunctureRate(puncture_rate));
+        }
+        Ok(FecCode::Punctured {
+            base: Box::new(base),
+            puncture_rate,
+        })
+    }
+
+    pub fn rate(&self) -> f64 {
+        match self {
+            FecCode::Uncoded => 1.0,
+            FecCode::Convolutional { rate } => *rate,
+            FecCode::Turbo { rate } => *rate,
+            FecCode::Ldpc { rate } => *rate,
+            FecCode::Custom { rate, .. } => *rate,
+            FecCode::WithFloor { rate, .. } => *rate,
+            FecCode::Concatenated { outer, inner } => outer.rate() * inner.rate(),
+            // removing bits raises the rate, but never above an uncoded stream
+            FecCode::Punctured { base, puncture_rate } => (base.rate() / puncture_rate).min(1.0),
+        }
+    }
+
+    pub fn from_family_and_rate(family: &str, rate: &str) -> Option<FecCode> {
+        // case-insensitive family name ("ldpc", "turbo", "conv") and a "k/n" rate
+        let rate: f64 = parse_code_rate(rate).ok()?;
+
+        match family.to_lowercase().as_str() {
+            "ldpc" => Some(FecCode::Ldpc { rate }),
+            "turbo" => Some(FecCode::Turbo { rate }),
+            "conv" | "convolutional" => Some(FecCode::Convolutional { rate }),
+            _ => None,
+        }
+    }
+
+    pub fn coding_gain_db(&self) -> f64 {
+        // nominal gain at BER 1e-5
+        match self {
+            FecCode::Uncoded => 0.0,
+            FecCode::Convolutional { rate } => interpolate_gain(&CONVOLUTIONAL_GAIN_TABLE, *rate),
+            FecCode::Turbo { rate } => interpolate_gain(&TURBO_GAIN_TABLE, *rate),
+            FecCode::Ldpc { rate } => interpolate_gain(&LDPC_GAIN_TABLE, *rate),
+            FecCode::Custom { coding_gain_db, .. } => *coding_gain_db,
+            FecCode::WithFloor { coding_gain_db, .. } => *coding_gain_db,
+            // gains add in dB, an optimistic but common first-order approximation
+            FecCode::Concatenated { outer, inner } => outer.coding_gain_db() + inner.coding_gain_db(),
+            FecCode::Punctured { base, .. } => {
+                // each punctured bit gives up redundancy, so the gain drops by the
+                // ratio of the punctured rate to the base rate (in dB)
+                let rate_increase_db: f64 = 10.0 * (self.rate() / base.rate()).log10();
+                (base.coding_gain_db() - rate_increase_db).max(0.0)
+            }
+        }
+    }
+
+    pub fn coding_gain_db_at_ber(&self, target_ber: f64) -> f64 {
+        // nominal gain moved along a straight line in log10(BER): more gain at lower BERs,
+        // less above 1e-5 (never below zero). A first-order fit, not a decoder simulation.
+        // Custom codes keep their stated gain, there is no family to take a slope from.
+        if target_ber.is_nan() || target_ber <= 0.0 {
+            return self.coding_gain_db();
+        }
+        let decades: f64 = (NOMINAL_GAIN_BER / target_ber).log10();
+
+        let slope: f64 = match self {
+            FecCode::Convolutional { .. } => CONVOLUTIONAL_GAIN_SLOPE_DB_PER_DECADE,
+            FecCode::Turbo { .. } | FecCode::Ldpc { .. } => CAPACITY_APPROACHING_GAIN_SLOPE_DB_PER_DECADE,
+            FecCode::Concatenated { outer, inner } => {
+                return outer.coding_gain_db_at_ber(target_ber) + inner.coding_gain_db_at_ber(target_ber);
+            }
+            FecCode::Punctured { base, .. } => {
+                let rate_increase_db: f64 = 10.0 * (self.rate() / base.rate()).log10();
+                return (base.coding_gain_db_at_ber(target_ber) - rate_increase_db).max(0.0);
+            }
+            _ => 0.0,
+        };
+
+        (self.coding_gain_db() + slope * decades).max(0.0)
+    }
+
+    pub fn error_floor(&self) -> f64 {
+        // lowest BER the decoder reaches, 0 when no floor is modeled
+        match self {
+            FecCode::WithFloor { floor_ber, .. } => *floor_ber,
+            // the outer code is there to clean up what the inner decoder leaves behind
+            FecCode::Concatenated { outer, .. } => outer.error_floor(),
+            FecCode::Punctured { base, .. } => base.error_floor(),
+            _ => 0.0,
+        }
+    }
+}
+
+impl Display for FecCode {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            FecCode::Uncoded => write!(f, "Uncoded"),
+            FecCode::Convolutional { rate } => write!(f, "Conv R={}", format_rate(*rate)),
+            FecCode::Turbo { rate } => write!(f, "Turbo R={}", format_rate(*rate)),
+            FecCode::Ldpc { rate } => write!(f, "LDPC R={}", format_rate(*rate)),
+            FecCode::Custom { rate, coding_gain_db } => {
+                write!(f, "Custom R={} ({} dB gain)", format_rate(*rate), coding_gain_db)
+            }
+            FecCode::WithFloor {
+                rate,
+                coding_gain_db,
+                floor_ber,
+            } => {
+                write!(f, "Custom R={} ({} dB gain, floor {:e})", format_rate(*rate), coding_gain_db, floor_ber)
+            }
+            FecCode::Concatenated { outer, inner } => write!(f, "{} + {}", outer, inner),
+            FecCode::Punctured { base, .. } => {
+                write!(f, "{} punctured to R={}", base, format_rate(self.rate()))
+            }
+        }
+    }
+}
+
+impl FromStr for FecCode {
+    type Err = FecCodeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // "<family> <k/n>" as written in a config file, e.g. "ldpc 3/4", or "uncoded"
+        let mut parts = s.split_whitespace();
+
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some(family), None, None) if family.eq_ignore_ascii_case("uncoded") => Ok(FecCode::Uncoded),
+            (Some(family), Some(rate), None) => {
+                parse_code_rate(rate)?;
+                FecCode::from_family_and_rate(family, rate)
+                    .ok_or_else(|| FecCodeError::UnknownFamily(family.to_string()))
+            }
+            _ => Err(FecCodeError::Malformed(s.to_string())),
+        }
+    }
+}
+
+pub fn parse_code_rate(rate: &str) -> Result<f64, FecCodeError> {
+    // "k/n" with 0 < k <= n, e.g. "3/4" -> 0.75
+    let invalid = || FecCodeError::InvalidRate(rate.to_string());
+
+    let (k, n) = rate.trim().split_once('/').ok_or_else(invalid)?;
+    let k: u32 = k.trim().parse().map_err(|_| invalid())?;
+    let n: u32 = n.trim().parse().map_err(|_| invalid())?;
+
+    if k == 0 || k > n {
+        return Err(invalid());
+    }
+
+    Ok(k as f64 / n as f64)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum FecCodeError {
+    Malformed(String),
+    InvalidRate(String),
+    UnknownFamily(String),
+    InvalidPunctureRate(f64),
+}
+
+impl Display for FecCodeError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            FecCodeError::Malformed(fec) => write!(f, "malformed FEC '{}', expected e.g. 'ldpc 3/4'", fec),
+            FecCodeError::InvalidRate(rate) => {
+                write!(f, "invalid code rate '{}', expected k/n with 0 < k <= n", rate)
+            }
+            FecCodeError::UnknownFamily(family) => write!(f, "unknown FEC family '{}'", family),
+            FecCodeError::InvalidPunctureRate(rate) => {
+                write!(f, "invalid puncture rate {}, expected a fraction in (0, 1]", rate)
+            }
+        }
+    }
+}
+
+impl std::error::Error for FecCodeError {}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CodedModulation {
+    pub modulation: Modulation,
+    pub fec: FecCode,
+}
+
+impl CodedModulation {
+    pub fn spectral_efficiency(&self) -> f64 {
+        // information bits per symbol (bits/s/Hz at a symbol rate equal to the bandwidth)
+        self.modulation.bits_per_symbol() as f64 * self.fec.rate()
+    }
+
+    pub fn symbol_rate(&self, info_bit_rate_bps: f64) -> f64 {
+        // symbols/s needed to carry the information bit rate
+        info_bit_rate_bps / self.spectral_efficiency()
+    }
+
+    pub fn throughput_bps(&self, bandwidth: f64) -> f64 {
+        // information bit rate with the symbol rate equal to the bandwidth
+        bandwidth * self.spectral_efficiency()
+    }
+
+    pub fn required_eb_no_db(&self, target_ber: f64) -> Option<f64> {
+        // Eb/No where ber_from_db meets the target, so the two always agree
+        // None below the error floor, or for a target the bracket can't reach (e.g. 0.6)
+        if target_ber.is_nan() || target_ber <= 0.0 || target_ber < self.fec.error_floor() {
+            return None;
+        }
+
+        let mut low: f64 = CODED_EB_NO_LOW_DB;
+        let mut high: f64 = CODED_EB_NO_HIGH_DB;
+        if self.ber_from_db(low) < target_ber || self.ber_from_db(high) > target_ber {
+            return None;
+        }
+
+        // the coded BER falls monotonically with Eb/No
+        for _ in 0..CODED_EB_NO_MAX_ITERATIONS {
+            let mid: f64 = (low + high) / 2.0;
+            let ber_mid: f64 = self.ber_from_db(mid);
+
+            if (ber_mid - target_ber).abs() / target_ber < CODED_EB_NO_TOLERANCE {
+                return Some(mid);
+            }
+
+            if ber_mid > target_ber {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+
+        Some((low + high) / 2.0)
+    }
+
+    pub fn required_eb_no_db_tabulated(&self, target_ber: f64) -> Option<f64> {
+        // published DVB-S2 threshold when the ModCod is in the standard, otherwise
+        // (custom codes, non-DVB-S2 rates) the coding gain model at target_ber
+        // The DVB-S2 thresholds are quasi-error-free and don't depend on target_ber
+        match dvbs2_required_es_no_db(self) {
+            Some(es_no_db) => Some(es_no_db - 10.0 * self.spectral_efficiency().log10()),
+            None => self.required_eb_no_db(target_ber),
+        }
+    }
+
+    pub fn ber_from_db(&self, eb_no_db: f64) -> f64 {
+        // the code shifts the uncoded BER curve left by its coding gain, down to the error floor
+        // The gain depends on the BER (coding_gain_db_at_ber), so it is read at the BER the
+        // nominal gain gives, which keeps the coded curve monotonic in Eb/No
+        let nominal_ber: f64 = ber::ber_from_db(eb_no_db + self.fec.coding_gain_db(), &self.modulation);
+        let coding_gain_db: f64 = self.fec.coding_gain_db_at_ber(nominal_ber);
+
+        ber::ber_from_db(eb_no_db + coding_gain_db, &self.modulation).max(self.fec.error_floor())
+    }
+
+    pub fn link_margin_db(&self, eb_no_db: f64, target_ber: f64) -> Option<f64> {
+        // positive margin means the link closes, None for an unreachable target BER
+        Some(eb_no_db - self.required_eb_no_db(target_ber)?)
+    }
+}
+
+pub fn dvbs2_qpsk_r34() -> CodedModulation {
+    CodedModulation {
+        modulation: Modulation::Qpsk,
+        fec: FecCode::Ldpc { rate: 0.75 },
+    }
+}
+
+pub fn dvbs2_16apsk_r34() -> CodedModulation {
+    CodedModulation {
+        modulation: Modulation::Apsk(16),
+        fec: FecCode::Ldpc { rate: 0.75 },
+    }
+}
+
+pub fn dvbs2_32apsk_r45() -> CodedModulation {
+    CodedModulation {
+        modulation: Modulation::Apsk(32),
+        fec: FecCode::Ldpc { rate: 0.8 },
+    }
+}
+
+pub fn dvbs2_required_es_no_db(modcod: &CodedModulation) -> Option<f64> {
+    // quasi-error-free Es/No from ETSI EN 302 307, None unless the ModCod is an LDPC
+    // rate and constellation pair defined by DVB-S2
+    let rate: f64 = match modcod.fec {
+        FecCode::Ldpc { rate } => rate,
+        _ => return None,
+    };
+
+    let table: &[(f64, f64)] = match modcod.modulation {
+        Modulation::Qpsk => &DVBS2_QPSK_ES_NO_TABLE,
+        Modulation::Mpsk(8) => &DVBS2_8PSK_ES_NO_TABLE,
+        Modulation::Apsk(16) => &DVBS2_16APSK_ES_NO_TABLE,
+        Modulation::Apsk(32) => &DVBS2_32APSK_ES_NO_TABLE,
+        _ => return None,
+    };
+
+    table
+        .iter()
+        .find(|(table_rate, _)| (table_rate - rate).abs() < 1e-9)
+        .map(|(_, es_no_db)| *es_no_db)
+}
+
+pub fn dvbs2_modcods() -> Vec<CodedModulation> {
+    // every ModCod with a DVB-S2 threshold, in table order
+    let tables: [(Modulation, &[(f64, f64)]); 4] = [
+        (Modulation::Qpsk, &DVBS2_QPSK_ES_NO_TABLE),
+        (Modulation::Mpsk(8), &DVBS2_8PSK_ES_NO_TABLE),
+        (Modulation::Apsk(16), &DVBS2_16APSK_ES_NO_TABLE),
+        (Modulation::Apsk(32), &DVBS2_32APSK_ES_NO_TABLE),
+    ];
+
+    tables
+        .iter()
+        .flat_map(|(modulation, table)| {
+            table.iter().map(|(rate, _)| CodedModulation {
+                modulation: *modulation,
+                fec: FecCode::Ldpc { rate: *rate },
+            })
+        })
+        .collect()
+}
+
+pub fn mod_cod_tradeoff(modcods: &[CodedModulation], target_ber: f64) -> Vec<(f64, f64)> {
+    // (spectral_efficiency, required_eb_no_db) per ModCod, the bandwidth vs power
+    // efficiency plane, using the DVB-S2 thresholds where they exist
+    // ModCods that can't reach target_ber are left out
+    // Write it out with plot::write_csv(&points, ("spectral_efficiency", "required_eb_no_db"), path)
+    modcods
+        .iter()
+        .filter_map(|modcod| Some((modcod.spectral_efficiency(), modcod.required_eb_no_db_tabulated(target_ber)?)))
+        .collect()
+}
+
+pub fn efficiency_frontier(points: &[(f64, f64)]) -> Vec<(f64, f64)> {
+    // Pareto frontier of (spectral_efficiency, required_eb_no_db) points, sorted by
+    // efficiency. A point is dropped when another is at least as efficient for no more Eb/No
+    let mut sorted: Vec<(f64, f64)> = points.to_vec();
+    sorted.sort_by(|a, b| b.0.total_cmp(&a.0).then(a.1.total_cmp(&b.1)));
+
+    let mut frontier: Vec<(f64, f64)> = Vec::new();
+    for point in sorted {
+        if frontier.last().map_or(true, |last: &(f64, f64)| point.1 < last.1) {
+            frontier.push(point);
+        }
+    }
+
+    frontier.reverse();
+    frontier
+}
+
+pub fn required_c_over_no_for_throughput(
+    modcod: &CodedModulation,
+    throughput_bps: f64,
+    rolloff: f64,
+    target_ber: f64,
+) -> Option<f64> {
+    // C/No (dB-Hz) needed to carry throughput_bps at target_ber
+    // The roll-off sets the occupied bandwidth, Rs * (1 + rolloff), but not the C/No:
+    // a matched filter's noise bandwidth is the symbol rate. None outside [0, 1]
+    if !(0.0..=1.0).contains(&rolloff) {
+        return None;
+    }
+
+    let symbol_rate: f64 = modcod.symbol_rate(throughput_bps);
+
+    // Es = (k * r) * Eb, in information bits
+    let es_over_no_db: f64 = modcod.required_eb_no_db(target_ber)? + 10.0 * modcod.spectral_efficiency().log10();
+
+    Some(energy::es_over_no_to_c_over_no(es_over_no_db, symbol_rate))
+}
+
+pub fn gap_to_capacity_db(modcod: &CodedModulation, target_ber: f64) -> Option<f64> {
+    // how far the ModCod sits from the Shannon limit at its own spectral efficiency
+    Some(modcod.required_eb_no_db(target_ber)? - phy::shannon_eb_no_limit_db(modcod.spectral_efficiency()))
+}
+
+pub fn total_degradation_curve(
+    modcod: &CodedModulation,
+    backoff_vs_ci: &[(f64, f64)],
+    target_ber: f64,
+) -> Vec<(f64, f64)> {
+    // (backoff_db, total_degradation_db) for each (backoff_db, c_over_i_db) amplifier point
+    // Total degradation is the backoff plus the extra C/N needed to hold the required
+    // Es/No once the intermod is combined in, 1/(C/(N+I)) = 1/(C/N) + 1/(C/I).
+    // Points where C/I alone is below the requirement can't close and are infinite.
+    let es_over_no_db: f64 = match modcod.required_eb_no_db(target_ber) {
+        Some(eb_no_db) => eb_no_db + 10.0 * modcod.spectral_efficiency().log10(),
+        None => return Vec::new(),
+    };
+    let required: f64 = 10.0_f64.powf(es_over_no_db / 10.0);
+
+    backoff_vs_ci
+        .iter()
+        .map(|&(backoff_db, c_over_i_db)| {
+            let c_over_i: f64 = 10.0_f64.powf(c_over_i_db / 10.0);
+            let remaining: f64 = 1.0 / required - 1.0 / c_over_i;
+
+            if remaining <= 0.0 {
+                return (backoff_db, f64::INFINITY);
+            }
+
+            let c_over_i_penalty_db: f64 = 10.0 * (1.0 / remaining).log10() - es_over_no_db;
+            (backoff_db, backoff_db + c_over_i_penalty_db)
+        })
+        .collect()
+}
+
+pub fn min_total_degradation(
+    modcod: &CodedModulation,
+    backoff_vs_ci: &[(f64, f64)],
+    target_ber: f64,
+) -> Option<(f64, f64)> {
+    // optimum operating point, (backoff_db, total_degradation_db)
+    total_degradation_curve(modcod, backoff_vs_ci, target_ber)
+        .into_iter()
+        .filter(|(_, degradation)| degradation.is_finite())
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+}
+
+impl Display for CodedModulation {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.modulation, self.fec)
+    }
+}
+
+fn interpolate_gain(table: &[(f64, f64)], rate: f64) -> f64 {
+    // clamp to the end points outside of the table
+    let (first_rate, first_gain) = table[0];
+    let (last_rate, last_gain) = table[table.len() - 1];
+
+    if rate <= first_rate {
+        return first_gain;
+    }
+    if rate >= last_rate {
+        return last_gain;
+    }
+
+    for window in table.windows(2) {
+        let (rate_low, gain_low) = window[0];
+        let (rate_high, gain_high) = window[1];
+        if rate <= rate_high {
+            return gain_low + (gain_high - gain_low) * (rate - rate_low) / (rate_high - rate_low);
+        }
+    }
+
+    last_gain
+}
+
+fn format_rate(rate: f64) -> String {
+    // prefer a k/n fraction when the rate is one, e.g. 0.75 -> "3/4"
+    for n in 1..=256_u32 {
+        let k: f64 = (rate * n as f64).round();
+        if (k / n as f64 - rate).abs() < 1e-9 {
+            return format!("{}/{}", k, n);
+        }
+    }
+    format!("{:.3}", rate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CodedModulation, FecCode, FecCodeError};
+    use crate::energy;
+    use crate::modulation::Modulation;
+
+    fn punctured_three_quarters() -> FecCode {
+        // rate-1/2 mother code, keep 2 of every 3 output bits
+        FecCode::Punctured {
+            base: Box::new(FecCode::Convolutional { rate: 0.5 }),
+            puncture_rate: 2.0 / 3.0,
+        }
+    }
+
+    #[test]
+    fn try_punctured() {
+        let base = FecCode::Convolutional { rate: 0.5 };
+
+        assert_eq!(
+            Ok(punctured_three_quarters()),
+            FecCode::try_punctured(base.clone(), 2.0 / 3.0)
+        );
+        assert_eq!(Err(FecCodeError::InvalidPunctureRate(0.0)), FecCode::try_punctured(base.clone(), 0.0));
+        assert_eq!(Err(FecCodeError::InvalidPunctureRate(-0.5)), FecCode::try_punctured(base.clone(), -0.5));
+        assert_eq!(Err(FecCodeError::InvalidPunctureRate(1.5)), FecCode::try_punctured(base.clone(), 1.5));
+        assert!(FecCode::try_punctured(base, f64::NAN).is_err());
+    }
+
+    #[test]
+    fn parse_code_rate() {
+        assert_eq!(Ok(0.75), super::parse_code_rate("3/4"));
+        assert_eq!(Ok(0.5), super::parse_code_rate(" 1 / 2 "));
+
+        for malformed in ["3/", "3-4", "0/4", "5/4", "a/b", "0.75"] {
+            assert_eq!(
+                Err(FecCodeError::InvalidRate(malformed.to_string())),
+                super::parse_code_rate(malformed)
+            );
+        }
+    }
+
+    #[test]
+    fn from_family_and_rate() {
+        assert_eq!(Some(FecCode::Ldpc { rate: 0.75 }), FecCode::from_family_and_rate("ldpc", "3/4"));
+        assert_eq!(Some(FecCode::Turbo { rate: 1.0 / 3.0 }), FecCode::from_family_and_rate("Turbo", "1/3"));
+        assert_eq!(
+            Some(FecCode::Convolutional { rate: 0.5 }),
+            FecCode::from_family_and_rate("conv", "1/2")
+        );
+        assert_eq!(None, FecCode::from_family_and_rate("polar", "1/2"));
+        assert_eq!(None, FecCode::from_family_and_rate("ldpc", "3:4"));
+    }
+
+    #[test]
+    fn fec_from_str() {
+        assert_eq!(Ok(FecCode::Ldpc { rate: 0.75 }), "ldpc 3/4".parse());
+        assert_eq!(Ok(FecCode::Uncoded), "uncoded".parse());
+
+        assert_eq!(Err(FecCodeError::UnknownFamily("polar".to_string())), "polar 1/2".parse::<FecCode>());
+        assert_eq!(Err(FecCodeError::InvalidRate("3/0".to_string())), "ldpc 3/0".parse::<FecCode>());
+        assert_eq!(Err(FecCodeError::Malformed("ldpc".to_string())), "ldpc".parse::<FecCode>());
+        assert_eq!(
+            "unknown FEC family 'polar'",
+            "polar 1/2".parse::<FecCode>().unwrap_err().to_string()
+        );
+    }
+
+    fn ldpc_with_floor() -> CodedModulation {
+        CodedModulation {
+            modulation: Modulation::Qpsk,
+            fec: FecCode::WithFloor {
+                rate: 0.5,
+                coding_gain_db: 8.6,
+                floor_ber: 1e-10,
+            },
+        }
+    }
+
+    #[test]
+    fn error_floor() {
+        let modcod = ldpc_with_floor();
+
+        // on the waterfall the floor doesn't matter
+        let plain = CodedModulation {
+            modulation: Modulation::Qpsk,
+            fec: FecCode::Custom {
+                rate: 0.5,
+                coding_gain_db: 8.6,
+            },
+        };
+        assert_eq!(plain.ber_from_db(1.0), modcod.ber_from_db(1.0));
+
+        // at high Eb/No the BER flattens instead of going to zero
+        assert_eq!(1e-10, modcod.ber_from_db(10.0));
+        assert_eq!(1e-10, modcod.ber_from_db(20.0));
+        assert!(plain.ber_from_db(10.0) < 1e-10);
+    }
+
+    #[test]
+    fn error_floor_limits_required_eb_no() {
+        let modcod = ldpc_with_floor();
+
+        assert!(modcod.required_eb_no_db(1e-9).is_some());
+        assert_eq!(None, modcod.required_eb_no_db(1e-12));
+        assert_eq!("QPSK Custom R=1/2 (8.6 dB gain, floor 1e-10)", modcod.to_string());
+    }
+
+    #[test]
+    fn coding_gain_grows_at_lower_ber() {
+        let ldpc = FecCode::Ldpc { rate: 0.5 };
+
+        assert_eq!(ldpc.coding_gain_db(), ldpc.coding_gain_db_at_ber(1e-5));
+        assert!(ldpc.coding_gain_db_at_ber(1e-8) > ldpc.coding_gain_db_at_ber(1e-5));
+        assert!((ldpc.coding_gain_db_at_ber(1e-8) - 8.6 - 3.0 * 0.7).abs() < 1e-9);
+        assert!(ldpc.coding_gain_db_at_ber(1e-3) < ldpc.coding_gain_db());
+
+        // a convolutional code gains less per decade
+        let conv = FecCode::Convolutional { rate: 0.5 };
+        let conv_growth: f64 = conv.coding_gain_db_at_ber(1e-8) - conv.coding_gain_db();
+        let ldpc_growth: f64 = ldpc.coding_gain_db_at_ber(1e-8) - ldpc.coding_gain_db();
+        assert!(conv_growth > 0.0 && conv_growth < ldpc_growth);
+    }
+
+    #[test]
+    fn required_eb_no_uses_ber_dependent_gain() {
+        let modcod = CodedModulation {
+            modulation: Modulation::Qpsk,
+            fec: FecCode::Ldpc { rate: 0.5 },
+        };
+
+        // more gain than the nominal 1e-5 figure, read along the coded curve so no more
+        // than the gain at the target itself
+        let uncoded: f64 = crate::ber::required_eb_no_db(1e-8, &Modulation::Qpsk).unwrap();
+        let required: f64 = modcod.required_eb_no_db(1e-8).unwrap();
+
+        assert!(required < uncoded - modcod.fec.coding_gain_db());
+        assert!(required > uncoded - modcod.fec.coding_gain_db_at_ber(1e-8) - 1e-9);
+    }
+
+    #[test]
+    fn interpolated_gain() {
+        let fec = FecCode::Ldpc { rate: 0.625 };
+
+        // halfway between the 1/2 and 3/4 table entries
+        assert_eq!(7.949999999999999, fec.coding_gain_db());
+    }
+
+    #[test]
+    fn concatenated_rate() {
+        let fec = FecCode::Concatenated {
+            outer: Box::new(FecCode::Custom {
+                rate: 223.0 / 255.0,
+                coding_gain_db: 2.0,
+            }),
+            inner: Box::new(FecCode::Convolutional { rate: 0.5 }),
+        };
+
+        assert_eq!(0.4372549019607843, fec.rate());
+        assert_eq!(7.0, fec.coding_gain_db());
+        assert_eq!("Custom R=223/255 (2 dB gain) + Conv R=1/2", fec.to_string());
+    }
+
+    #[test]
+    fn punctured_rate() {
+        let fec = punctured_three_quarters();
+
+        assert_eq!(0.75, fec.rate());
+    }
+
+    #[test]
+    fn punctured_gain() {
+        let fec = punctured_three_quarters();
+
+        // 5 dB mother code gain less 10*log10(1.5)
+        assert_eq!(3.2390874094431876, fec.coding_gain_db());
+        assert!(fec.coding_gain_db() < FecCode::Convolutional { rate: 0.5 }.coding_gain_db());
+    }
+
+    #[test]
+    fn punctured_rate_never_exceeds_one() {
+        let fec = FecCode::Punctured {
+            base: Box::new(FecCode::Convolutional { rate: 0.5 }),
+            puncture_rate: 0.25,
+        };
+
+        assert_eq!(1.0, fec.rate());
+        assert_eq!(1.989700043360188, fec.coding_gain_db());
+    }
+
+    #[test]
+    fn punctured_display() {
+        let fec = punctured_three_quarters();
+
+        assert_eq!("Conv R=1/2 punctured to R=3/4", fec.to_string());
+    }
+
+    #[test]
+    fn punctured_spectral_efficiency() {
+        let modcod = CodedModulation {
+            modulation: Modulation::Qpsk,
+            fec: punctured_three_quarters(),
+        };
+
+        assert_eq!(1.5, modcod.spectral_efficiency());
+        assert_eq!("QPSK Conv R=1/2 punctured to R=3/4", modcod.to_string());
+    }
+
+    #[test]
+    fn symbol_rate() {
+        let modcod = CodedModulation {
+            modulation: Modulation::Qpsk,
+            fec: FecCode::Ldpc { rate: 0.75 },
+        };
+
+        // 1.5 bits per symbol
+        assert_eq!(36.0e6, modcod.symbol_rate(54.0e6));
+    }
+
+    #[test]
+    fn required_c_over_no_for_throughput() {
+        let modcod = CodedModulation {
+            modulation: Modulation::Qpsk,
+            fec: FecCode::Ldpc { rate: 0.5 },
+        };
+
+        let c_over_no: f64 = super::required_c_over_no_for_throughput(&modcod, 10.0e6, 0.2, 1e-5).unwrap();
+
+        // same answer as going straight from Eb/No through the bit rate
+        let expected: f64 = energy::eb_over_no_to_c_over_no(modcod.required_eb_no_db(1e-5).unwrap(), 10.0e6);
+        assert!((c_over_no - expected).abs() < 1e-9);
+
+        // the roll-off doesn't change the answer, but must be a valid factor
+        let wide: f64 = super::required_c_over_no_for_throughput(&modcod, 10.0e6, 1.0, 1e-5).unwrap();
+        assert_eq!(c_over_no, wide);
+        assert_eq!(None, super::required_c_over_no_for_throughput(&modcod, 10.0e6, -0.1, 1e-5));
+        assert_eq!(None, super::required_c_over_no_for_throughput(&modcod, 10.0e6, 1.5, 1e-5));
+        assert_eq!(None, super::required_c_over_no_for_throughput(&modcod, 10.0e6, f64::NAN, 1e-5));
+    }
+
+    #[test]
+    fn doubling_throughput_needs_three_db() {
+        let modcod = CodedModulation {
+            modulation: Modulation::Mpsk(8),
+            fec: FecCode::Ldpc { rate: 2.0 / 3.0 },
+        };
+
+        let single: f64 = super::required_c_over_no_for_throughput(&modcod, 50.0e6, 0.2, 1e-5).unwrap();
+        let double: f64 = super::required_c_over_no_for_throughput(&modcod, 100.0e6, 0.2, 1e-5).unwrap();
+
+        assert!((double - single - 10.0 * 2.0_f64.log10()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn throughput_bps() {
+        let modcod = super::dvbs2_qpsk_r34();
+
+        assert_eq!(54.0e6, modcod.throughput_bps(36.0e6));
+    }
+
+    #[test]
+    fn coded_ber_beats_uncoded() {
+        let modcod = super::dvbs2_qpsk_r34();
+
+        let coded: f64 = modcod.ber_from_db(4.0);
+        let uncoded: f64 = crate::ber::ber_from_db(4.0, &Modulation::Qpsk);
+
+        assert!(coded < uncoded);
+    }
+
+    #[test]
+    fn link_margin_db() {
+        let modcod = super::dvbs2_qpsk_r34();
+        let required: f64 = modcod.required_eb_no_db(1e-5).unwrap();
+
+        assert_eq!(Some(0.0), modcod.link_margin_db(required, 1e-5));
+        assert!((modcod.link_margin_db(required + 3.0, 1e-5).unwrap() - 3.0).abs() < 1e-12);
+        assert_eq!(None, modcod.link_margin_db(required, 0.6));
+    }
+
+    #[test]
+    fn dvbs2_required_es_no_db() {
+        let qpsk_half = CodedModulation {
+            modulation: Modulation::Qpsk,
+            fec: FecCode::Ldpc { rate: 0.5 },
+        };
+        let psk8_two_thirds = CodedModulation {
+            modulation: Modulation::Mpsk(8),
+            fec: FecCode::Ldpc { rate: 2.0 / 3.0 },
+        };
+
+        assert_eq!(Some(1.0), super::dvbs2_required_es_no_db(&qpsk_half));
+        assert_eq!(Some(6.62), super::dvbs2_required_es_no_db(&psk8_two_thirds));
+        assert_eq!(Some(4.03), super::dvbs2_required_es_no_db(&super::dvbs2_qpsk_r34()));
+        assert_eq!(Some(10.21), super::dvbs2_required_es_no_db(&super::dvbs2_16apsk_r34()));
+        assert_eq!(Some(13.64), super::dvbs2_required_es_no_db(&super::dvbs2_32apsk_r45()));
+    }
+
+    #[test]
+    fn dvbs2_required_es_no_db_unknown() {
+        // not a DVB-S2 rate, constellation, or code
+        let odd_rate = CodedModulation {
+            modulation: Modulation::Qpsk,
+            fec: FecCode::Ldpc { rate: 0.7 },
+        };
+        let qam = CodedModulation {
+            modulation: Modulation::Mqam(16),
+            fec: FecCode::Ldpc { rate: 0.75 },
+        };
+        let turbo = CodedModulation {
+            modulation: Modulation::Qpsk,
+            fec: FecCode::Turbo { rate: 0.5 },
+        };
+
+        assert_eq!(None, super::dvbs2_required_es_no_db(&odd_rate));
+        assert_eq!(None, super::dvbs2_required_es_no_db(&qam));
+        assert_eq!(None, super::dvbs2_required_es_no_db(&turbo));
+    }
+
+    #[test]
+    fn required_eb_no_db_tabulated() {
+        // QPSK 3/4 carries 1.5 bits per symbol, Eb/No = 4.03 - 10*log10(1.5)
+        let modcod = super::dvbs2_qpsk_r34();
+        let expected: f64 = 4.03 - 10.0 * 1.5_f64.log10();
+
+        assert!((modcod.required_eb_no_db_tabulated(1e-5).unwrap() - expected).abs() < 1e-12);
+
+        // custom codes fall back to the coding gain model
+        let custom = CodedModulation {
+            modulation: Modulation::Qpsk,
+            fec: FecCode::Custom {
+                rate: 0.5,
+                coding_gain_db: 6.0,
+            },
+        };
+        assert_eq!(custom.required_eb_no_db(1e-5), custom.required_eb_no_db_tabulated(1e-5));
+    }
+
+    #[test]
+    fn dvbs2_apsk_presets() {
+        assert_eq!("16-APSK LDPC R=3/4", super::dvbs2_16apsk_r34().to_string());
+        assert_eq!(3.0, super::dvbs2_16apsk_r34().spectral_efficiency());
+        assert_eq!("32-APSK LDPC R=4/5", super::dvbs2_32apsk_r45().to_string());
+        assert_eq!(4.0, super::dvbs2_32apsk_r45().spectral_efficiency());
+    }
+
+    #[test]
+    fn gap_to_capacity_db() {
+        // DVB-S2 LDPC sits a few dB from capacity
+        for modcod in [super::dvbs2_qpsk_r34(), super::dvbs2_16apsk_r34(), super::dvbs2_32apsk_r45()] {
+            let gap: f64 = super::gap_to_capacity_db(&modcod, 1e-5).unwrap();
+
+            assert!(gap > 0.5 && gap < 5.0, "{} gap {} dB", modcod, gap);
+        }
+
+        assert_eq!(None, super::gap_to_capacity_db(&super::dvbs2_qpsk_r34(), 0.6));
+    }
+
+    #[test]
+    fn min_total_degradation() {
+        let modcod = super::dvbs2_qpsk_r34();
+
+        // synthetic amplifier, C/I improves 3 dB per dB of backoff
+        let backoff_vs_ci: Vec<(f64, f64)> = (0..=100)
+            .map(|i| {
+                let backoff_db: f64 = i as f64 * 0.1;
+                (backoff_db, 2.0 + 3.0 * backoff_db)
+            })
+            .collect();
+
+        let curve = super::total_degradation_curve(&modcod, &backoff_vs_ci, 1e-5);
+        assert_eq!(backoff_vs_ci.len(), curve.len());
+        // no backoff at all can't close the link through the intermod
+        assert_eq!(f64::INFINITY, curve[0].1);
+
+        let (backoff_db, degradation_db) = super::min_total_degradation(&modcod, &backoff_vs_ci, 1e-5).unwrap();
+
+        // the slopes balance where d(penalty)/d(backoff) = -1, which for a 3 dB/dB
+        // intermod curve is C/I four times (6 dB above) the required Es/No
+        let es_over_no_db: f64 = modcod.required_eb_no_db(1e-5).unwrap() + 10.0 * 1.5_f64.log10();
+        let c_over_i_db: f64 = 2.0 + 3.0 * backoff_db;
+        assert!((c_over_i_db - es_over_no_db - 10.0 * 4.0_f64.log10()).abs() < 0.3);
+
+        assert!(curve.iter().all(|(_, d)| *d >= degradation_db));
+    }
+
+    #[test]
+    fn mod_cod_tradeoff() {
+        let modcods: Vec<CodedModulation> = super::dvbs2_modcods();
+        let points: Vec<(f64, f64)> = super::mod_cod_tradeoff(&modcods, 1e-7);
+
+        assert_eq!(28, points.len());
+        // QPSK 3/4 at 1.5 bit/s/Hz needs 4.03 - 10*log10(1.5) dB
+        assert!((points[6].0 - 1.5).abs() < 1e-12);
+        assert!((points[6].1 - (4.03 - 10.0 * 1.5_f64.log10())).abs() < 1e-12);
+    }
+
+    #[test]
+    fn efficiency_frontier_is_monotone() {
+        let points: Vec<(f64, f64)> = super::mod_cod_tradeoff(&super::dvbs2_modcods(), 1e-7);
+        let frontier: Vec<(f64, f64)> = super::efficiency_frontier(&points);
+
+        assert!(frontier.len() > 1 && frontier.len() < points.len());
+        for pair in frontier.windows(2) {
+            assert!(pair[1].0 > pair[0].0);
+            assert!(pair[1].1 > pair[0].1);
+        }
+
+        // per information bit QPSK 1/4 needs more Eb/No than 1/3 and falls off the frontier,
+        // which runs from QPSK 1/3 to 32APSK 9/10
+        assert!((frontier[0].0 - 2.0 / 3.0).abs() < 1e-12);
+        assert!((frontier[frontier.len() - 1].0 - 4.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn efficiency_frontier_drops_dominated_points() {
+        let points: [(f64, f64); 4] = [(2.0, 5.0), (1.0, 2.0), (1.5, 6.0), (2.0, 4.0)];
+
+        assert_eq!(vec![(1.0, 2.0), (2.0, 4.0)], super::efficiency_frontier(&points));
+    }
+
+    #[test]
+    fn required_eb_no_round_trip() {
+        // LDPC R=1/2 QPSK, away from the 1e-5 the nominal gains are quoted at
+        let modcod = CodedModulation {
+            modulation: Modulation::Qpsk,
+            fec: FecCode::Ldpc { rate: 0.5 },
+        };
+
+        for target in [1e-3, 1e-5, 1e-8] {
+            let eb_no_db: f64 = modcod.required_eb_no_db(target).unwrap();
+
+            assert!((modcod.ber_from_db(eb_no_db) / target - 1.0).abs() < 1e-5);
+            assert!(modcod.link_margin_db(eb_no_db, target).unwrap().abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn coded_requirement_is_monotonic() {
+        let modcod = CodedModulation {
+            modulation: Modulation::Qpsk,
+            fec: FecCode::Ldpc { rate: 0.5 },
+        };
+
+        let mut previous: f64 = f64::NEG_INFINITY;
+        for exponent in 3..=12 {
+            let eb_no_db: f64 = modcod.required_eb_no_db(10.0_f64.powi(-exponent)).unwrap();
+
+            assert!(eb_no_db > previous);
+            previous = eb_no_db;
+        }
+    }
+}
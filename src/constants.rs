@@ -7,6 +7,24 @@ pub const MASS_OF_EARTH: f64 = 5.972e24;
 // 6.67430(15)×10−11 m3⋅kg−1⋅s−2
 pub const GRAVITATIONAL_CONSTANT: f64 = 0.0000000000667430;
 
+pub const RADIUS_OF_MOON: f64 = 1737400.0;
+pub const MASS_OF_MOON: f64 = 7.342e22;
+
+// standard gravitational parameters (GM, m^3/s^2) of the masses above,
+// orbits::calculate_standard_gravitational_parameter for any other body
+pub const MU_EARTH: f64 = GRAVITATIONAL_CONSTANT * MASS_OF_EARTH;
+pub const MU_MOON: f64 = GRAVITATIONAL_CONSTANT * MASS_OF_MOON;
+
+// https://physics.nist.gov/cgi-bin/cuu/Value?k
+// 1.380649×10−23 J⋅K−1, exact since the 2019 SI redefinition
+pub const BOLTZMANN_CONSTANT: f64 = 1.380649e-23;
+
+// IEEE standard reference temperature T0 for noise figure (K)
+pub const REFERENCE_TEMPERATURE: f64 = 290.0;
+
+// thermal noise density at T0, 10 * log10(k * T0) + 30, the "-174 dBm/Hz"
+pub const KT0_DBM_PER_HZ: f64 = -173.97518719422808;
+
 #[cfg(test)]
 mod tests {
 
@@ -26,4 +44,27 @@ mod tests {
         let expected: f64 = 6.67430 * BASE_TEN.powf(POWER_OF_NEGATIVE_ELEVEN);
         assert_eq!(expected, GRAVITATIONAL_CONSTANT);
     }
+
+    #[test]
+    fn gravitational_parameters() {
+        use super::{GRAVITATIONAL_CONSTANT, MASS_OF_EARTH, MASS_OF_MOON, MU_EARTH, MU_MOON};
+
+        assert_eq!(GRAVITATIONAL_CONSTANT * MASS_OF_EARTH, MU_EARTH);
+        assert_eq!(GRAVITATIONAL_CONSTANT * MASS_OF_MOON, MU_MOON);
+
+        // ~3.986e14 and ~4.9e12 m^3/s^2
+        assert!((MU_EARTH / 3.986e14 - 1.0).abs() < 1e-3);
+        assert!((MU_MOON / 4.9e12 - 1.0).abs() < 1e-2);
+    }
+
+    #[test]
+    fn kt0_dbm_per_hz() {
+        use super::{BOLTZMANN_CONSTANT, KT0_DBM_PER_HZ, REFERENCE_TEMPERATURE};
+
+        let derived: f64 = 10.0 * (BOLTZMANN_CONSTANT * REFERENCE_TEMPERATURE).log10() + 30.0;
+        assert!((derived - KT0_DBM_PER_HZ).abs() < 1e-12);
+
+        // the textbook -174 dBm/Hz is this value rounded
+        assert!((KT0_DBM_PER_HZ + 174.0).abs() < 0.03);
+    }
 }
@@ -1,5 +1,7 @@
+use crate::constants::REFERENCE_TEMPERATURE;
+
 pub fn noise_temperature_from_noise_factor(noise_factor: f64) -> f64 {
-    290.0 * (noise_factor - 1.0)
+    REFERENCE_TEMPERATURE * (noise_factor - 1.0)
 }
 
 pub fn noise_temperature_from_noise_figure(noise_figure: f64) -> f64 {
@@ -12,7 +14,7 @@ pub fn noise_factor_from_noise_figure(noise_figure: f64) -> f64 {
 }
 
 pub fn noise_factor_from_noise_temperature(noise_temperature: f64) -> f64 {
-    1.0 + (noise_temperature / 290.0)
+    1.0 + (noise_temperature / REFERENCE_TEMPERATURE)
 }
 
 pub fn noise_figure_from_noise_temperature(noise_temperature: f64) -> f64 {
@@ -24,8 +26,24 @@ pub fn noise_figure_from_noise_factor(noise_factor: f64) -> f64 {
     10.0_f64 * noise_factor.log10()
 }
 
+pub fn friis_cascade_noise_figure_db(stages: &[(f64, f64)]) -> f64 {
+    // Friis formula, each stage is (gain_db, noise_figure_db) in signal order
+    // F_total = F1 + (F2 - 1)/G1 + (F3 - 1)/(G1*G2) + ...
+    // Passive loss stages are entered as negative gain with noise figure equal to the loss
+    let mut total_noise_factor: f64 = 1.0;
+    let mut preceding_gain: f64 = 1.0;
+
+    for (gain_db, noise_figure_db) in stages {
+        let noise_factor: f64 = noise_factor_from_noise_figure(*noise_figure_db);
+        total_noise_factor += (noise_factor - 1.0) / preceding_gain;
+        preceding_gain *= 10.0_f64.powf(gain_db / 10.0);
+    }
+
+    noise_figure_from_noise_factor(total_noise_factor)
+}
+
 pub fn noise_power_from_bandwidth(temperature: f64, bandwidth: f64) -> f64 {
-    1.38e-23 * temperature * bandwidth
+    crate::constants::BOLTZMANN_CONSTANT * temperature * bandwidth
 }
 
 // Noise Figure of Passive Device
@@ -36,6 +54,17 @@ pub fn noise_power_from_bandwidth(temperature: f64, bandwidth: f64) -> f64 {
 // Where G is the gain of the device (less than or equal to 1), and Tp is the physical temperature of the device. Therefore, I would recommend that the statement should say, "Linear passive devices at room temperature have a noise figure equal to their loss. Expressed in dB, the NF is equal to -S21(dB). Something with one dB loss has one dB noise figure at room temperature." I know that the NF wouldn't change very much if the device is at a physical temperature near room temperature, but if some poor slob is working at temperatures very different than room temperature, their assumption that the NF would be equal to the loss would be incorrect.
 // I hope that this helps."
 
+pub fn passive_device_noise_temperature_k(loss_db: f64, physical_temp_k: f64) -> f64 {
+    // Te = (1/G - 1) * Tp = (L - 1) * Tp, referenced to the input
+    let loss: f64 = 10.0_f64.powf(loss_db / 10.0);
+    (loss - 1.0) * physical_temp_k
+}
+
+pub fn passive_device_noise_figure_db(loss_db: f64, physical_temp_k: f64) -> f64 {
+    // F = 1 + (1/G - 1) * Tp/To, equal to the loss only at To = 290 K
+    noise_figure_from_noise_temperature(passive_device_noise_temperature_k(loss_db, physical_temp_k))
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -156,6 +185,47 @@ mod tests {
 
         let noise_power_dbm: f64 = 10.0 * (noise_power.log10() + 3.0);
 
-        assert_eq!(-93.97722915699808, noise_power_dbm);
+        assert_eq!(-93.97518719422811, noise_power_dbm);
+    }
+
+    #[test]
+    fn friis_single_stage() {
+        let noise_figure: f64 = super::friis_cascade_noise_figure_db(&[(20.0, 1.0)]);
+
+        assert!((noise_figure - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn friis_lna_then_mixer() {
+        // 20 dB LNA with 1 dB NF ahead of a 10 dB NF mixer
+        // F = 1.259 + (10 - 1)/100 = 1.349
+        let noise_figure: f64 = super::friis_cascade_noise_figure_db(&[(20.0, 1.0), (10.0, 10.0)]);
+
+        assert_eq!(1.2998793622358962, noise_figure);
+    }
+
+    #[test]
+    fn friis_passive_loss_first() {
+        // 2 dB cable loss ahead of the same LNA adds its loss directly
+        let noise_figure: f64 = super::friis_cascade_noise_figure_db(&[(-2.0, 2.0), (20.0, 1.0)]);
+
+        assert!((noise_figure - 3.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn passive_device_at_room_temperature() {
+        let noise_figure: f64 = super::passive_device_noise_figure_db(1.0, 290.0);
+
+        assert!((noise_figure - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn cooled_passive_device() {
+        // a 3 dB attenuator at 20 K adds ~20 K, well under its loss
+        let noise_figure: f64 = super::passive_device_noise_figure_db(3.0, 20.0);
+        let noise_temperature: f64 = super::passive_device_noise_temperature_k(3.0, 20.0);
+
+        assert!(noise_figure < 3.0);
+        assert!((noise_temperature - 19.90524629937759).abs() < 1e-9);
     }
 }
@@ -0,0 +1,117 @@
+// Reductions over (x, y) curves such as ber::ber_curve and doppler::doppler_curve
+// All return None for an empty curve
+
+pub fn peak(points: &[(f64, f64)]) -> Option<(f64, f64)> {
+    // point with the largest y
+    points.iter().copied().max_by(|a, b| a.1.total_cmp(&b.1))
+}
+
+pub fn min(points: &[(f64, f64)]) -> Option<(f64, f64)> {
+    // point with the smallest y
+    points.iter().copied().min_by(|a, b| a.1.total_cmp(&b.1))
+}
+
+pub fn mean_y(points: &[(f64, f64)]) -> Option<f64> {
+    // arithmetic mean of the samples, not weighted by the x spacing
+    if points.is_empty() {
+        return None;
+    }
+
+    Some(points.iter().map(|(_, y)| y).sum::<f64>() / points.len() as f64)
+}
+
+pub fn interp_y(points: &[(f64, f64)], x: f64) -> Option<f64> {
+    // linear interpolation of y at x, the points must be sorted by ascending x
+    // None when x falls outside the curve (no extrapolation)
+    let (first, last) = (points.first()?, points.last()?);
+    if x.is_nan() || x < first.0 || x > last.0 {
+        return None;
+    }
+
+    // first segment whose right end reaches x
+    let index: usize = points.partition_point(|(px, _)| *px < x);
+    if index == 0 {
+        return Some(first.1);
+    }
+
+    let (x0, y0) = points[index - 1];
+    let (x1, y1) = points[index];
+
+    Some(y0 + (y1 - y0) * (x - x0) / (x1 - x0))
+}
+
+#[cfg(test)]
+mod tests {
+
+    fn triangle() -> Vec<(f64, f64)> {
+        // rises to 4 at x = 2, then falls to -2
+        vec![(0.0, 0.0), (1.0, 2.0), (2.0, 4.0), (3.0, 1.0), (4.0, -2.0)]
+    }
+
+    #[test]
+    fn peak() {
+        assert_eq!(Some((2.0, 4.0)), super::peak(&triangle()));
+    }
+
+    #[test]
+    fn min() {
+        assert_eq!(Some((4.0, -2.0)), super::min(&triangle()));
+    }
+
+    #[test]
+    fn mean_y() {
+        assert_eq!(Some(1.0), super::mean_y(&triangle()));
+    }
+
+    #[test]
+    fn empty_curve() {
+        assert_eq!(None, super::peak(&[]));
+        assert_eq!(None, super::min(&[]));
+        assert_eq!(None, super::mean_y(&[]));
+    }
+
+    #[test]
+    fn doppler_curve_is_symmetric() {
+        // an overhead pass runs from +fd to -fd
+        let curve = crate::doppler::doppler_curve(12.0e9, 7.6e3, 550.0e3, crate::constants::RADIUS_OF_EARTH, 101);
+
+        let (_, high) = super::peak(&curve).unwrap();
+        let (_, low) = super::min(&curve).unwrap();
+
+        assert!((high + low).abs() < 1e-6);
+        assert!(super::mean_y(&curve).unwrap().abs() < 1e-6);
+    }
+
+    #[test]
+    fn interp_y() {
+        let curve = triangle();
+
+        assert_eq!(Some(3.0), super::interp_y(&curve, 1.5));
+        assert_eq!(Some(-0.5), super::interp_y(&curve, 3.5));
+
+        // on a sample and at both ends
+        assert_eq!(Some(4.0), super::interp_y(&curve, 2.0));
+        assert_eq!(Some(0.0), super::interp_y(&curve, 0.0));
+        assert_eq!(Some(-2.0), super::interp_y(&curve, 4.0));
+    }
+
+    #[test]
+    fn interp_y_out_of_range() {
+        let curve = triangle();
+
+        assert_eq!(None, super::interp_y(&curve, -0.1));
+        assert_eq!(None, super::interp_y(&curve, 4.1));
+        assert_eq!(None, super::interp_y(&curve, f64::NAN));
+        assert_eq!(None, super::interp_y(&[], 0.0));
+    }
+
+    #[test]
+    fn interp_ber_curve() {
+        // between samples of a 1 dB QPSK curve, close to the exact BER at 6.5 dB
+        let curve = crate::ber::ber_curve(&crate::modulation::Modulation::Qpsk, 0.0, 10.0, 1.0);
+        let ber: f64 = super::interp_y(&curve, 6.5).unwrap();
+        let exact: f64 = crate::ber::ber_from_db(6.5, &crate::modulation::Modulation::Qpsk);
+
+        assert!(ber > exact && ber < 2.0 * exact);
+    }
+}
@@ -0,0 +1,255 @@
+use crate::constants::SPEED_OF_LIGHT;
+
+// Radial velocity is positive when the transmitter and receiver are closing (approaching)
+
+pub fn doppler_shift_hz(frequency_hz: f64, radial_velocity_m_s: f64) -> f64 {
+    // classical (non-relativistic) shift, fd = f * v / c
+    frequency_hz * radial_velocity_m_s / SPEED_OF_LIGHT
+}
+
+pub fn doppler_shift_relativistic_hz(frequency_hz: f64, radial_velocity_m_s: f64) -> f64 {
+    // longitudinal relativistic shift, fd = f * (sqrt((1 + b) / (1 - b)) - 1), b = v/c
+    // Differs from the classical shift by ~f * b^2 / 2, well under a hertz at LEO speeds
+    // below X-band; it matters for precise timing, ranging, and b approaching 0.01 or more
+    let beta: f64 = radial_velocity_m_s / SPEED_OF_LIGHT;
+
+    frequency_hz * (((1.0 + beta) / (1.0 - beta)).sqrt() - 1.0)
+}
+
+pub fn doppler_received_frequency(frequency_hz: f64, radial_velocity_m_s: f64) -> f64 {
+    frequency_hz + doppler_shift_hz(frequency_hz, radial_velocity_m_s)
+}
+
+pub fn doppler_two_way_received_frequency(frequency_hz: f64, radial_velocity_m_s: f64) -> f64 {
+    // shifted on the uplink, then again on the downlink through a bent-pipe or coherent transponder
+    // f * (1 + v/c)^2, ignoring the transponder's frequency translation (turnaround) ratio,
+    // which scales the downlink shift by the ratio of the two carriers
+    let uplink: f64 = doppler_received_frequency(frequency_hz, radial_velocity_m_s);
+
+    doppler_received_frequency(uplink, radial_velocity_m_s)
+}
+
+pub fn doppler_two_way_shift_hz(frequency_hz: f64, radial_velocity_m_s: f64) -> f64 {
+    // ~2 * f * v / c for v << c
+    doppler_two_way_received_frequency(frequency_hz, radial_velocity_m_s) - frequency_hz
+}
+
+pub fn doppler_rate_hz_per_s(frequency_hz: f64, radial_acceleration_m_s2: f64) -> f64 {
+    // rate of change of the shift, df/dt = f * a / c
+    frequency_hz * radial_acceleration_m_s2 / SPEED_OF_LIGHT
+}
+
+pub fn max_doppler_rate_circular(orbital_speed_m_s: f64, altitude_m: f64, body_radius_m: f64, frequency_hz: f64) -> f64 {
+    // Peak Doppler rate magnitude (Hz/s) for a circular orbit passing directly overhead
+    // The peak occurs at zenith (closest approach), where the shift itself is zero
+    // and the range acceleration is v^2 * R / (h * (R + h)), ignoring the body's rotation
+    let orbit_radius: f64 = body_radius_m + altitude_m;
+    let radial_acceleration: f64 =
+        orbital_speed_m_s * orbital_speed_m_s * body_radius_m / (altitude_m * orbit_radius);
+
+    doppler_rate_hz_per_s(frequency_hz, radial_acceleration)
+}
+
+pub fn leo_max_doppler_hz(frequency_hz: f64, altitude_m: f64, mass_of_body: f64, body_radius_m: f64) -> f64 {
+    // worst-case shift magnitude, at the horizon of an overhead pass
+    // there the line of sight takes R / (R + h) of the circular orbit speed
+    let orbit_radius: f64 = body_radius_m + altitude_m;
+    let orbital_speed: f64 = crate::orbits::circular::calculate_circular_orbit_speed(mass_of_body, orbit_radius);
+
+    doppler_shift_hz(frequency_hz, orbital_speed * body_radius_m / orbit_radius)
+}
+
+pub fn doppler_curve(
+    frequency_hz: f64,
+    orbital_speed_m_s: f64,
+    altitude_m: f64,
+    body_radius_m: f64,
+    samples: usize,
+) -> Vec<(f64, f64)> {
+    // (time_s, shift_hz) for a circular orbit passing directly overhead,
+    // from horizon-rise (t = 0) to horizon-set, ignoring the body's rotation
+    let orbit_radius: f64 = body_radius_m + altitude_m;
+    let angular_rate: f64 = orbital_speed_m_s / orbit_radius; // rad/s
+
+    // Earth central angle where the satellite is on the horizon
+    let max_central_angle: f64 = (body_radius_m / orbit_radius).acos();
+    let pass_duration: f64 = 2.0 * max_central_angle / angular_rate;
+
+    (0..samples)
+        .map(|i| {
+            let time: f64 = if samples > 1 {
+                pass_duration * i as f64 / (samples - 1) as f64
+            } else {
+                pass_duration / 2.0
+            };
+
+            // negative before closest approach
+            let central_angle: f64 = angular_rate * time - max_central_angle;
+
+            // law of cosines for the range, differentiated with respect to time
+            let range: f64 = (body_radius_m * body_radius_m + orbit_radius * orbit_radius
+                - 2.0 * body_radius_m * orbit_radius * central_angle.cos())
+            .sqrt();
+            let range_rate: f64 =
+                body_radius_m * orbit_radius * central_angle.sin() * angular_rate / range;
+
+            // a shrinking range is a closing (positive) radial velocity
+            (time, doppler_shift_hz(frequency_hz, -range_rate))
+        })
+        .collect()
+}
+
+pub fn precompensation_schedule(
+    frequency_hz: f64,
+    orbital_speed_m_s: f64,
+    altitude_m: f64,
+    body_radius_m: f64,
+    samples: usize,
+) -> Vec<(f64, f64)> {
+    // (time_s, tune_frequency_hz) for the same overhead pass as doppler_curve
+    // Tuning the ground transmitter to the nominal frequency less the predicted shift
+    // lands the carrier on frequency_hz at the satellite (to first order in v/c)
+    doppler_curve(frequency_hz, orbital_speed_m_s, altitude_m, body_radius_m, samples)
+        .into_iter()
+        .map(|(time, shift)| (time, frequency_hz - shift))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::constants::{MASS_OF_EARTH, RADIUS_OF_EARTH, SPEED_OF_LIGHT};
+    use crate::orbits::circular::calculate_circular_orbit_speed;
+
+    #[test]
+    fn approaching() {
+        // 7.5 km/s closing at 12 GHz
+        let shift: f64 = super::doppler_shift_hz(12.0e9, 7500.0);
+
+        assert_eq!(300207.6856783368, shift);
+    }
+
+    #[test]
+    fn receding() {
+        let frequency: f64 = 12.0e9;
+        let received: f64 = super::doppler_received_frequency(frequency, -7500.0);
+
+        assert!(received < frequency);
+    }
+
+    #[test]
+    fn doppler_rate() {
+        // 10 m/s^2 at 12 GHz
+        let rate: f64 = super::doppler_rate_hz_per_s(12.0e9, 10.0);
+
+        assert_eq!(400.2769142377825, rate);
+    }
+
+    #[test]
+    fn leo_ku_band_max_rate() {
+        let altitude: f64 = 550.0e3;
+        let orbital_speed: f64 = calculate_circular_orbit_speed(MASS_OF_EARTH, RADIUS_OF_EARTH + altitude);
+
+        let rate: f64 = super::max_doppler_rate_circular(orbital_speed, altitude, RADIUS_OF_EARTH, 12.0e9);
+
+        // kHz/s for a 550 km LEO at Ku-band
+        assert!(rate > 1.0e3 && rate < 10.0e3);
+    }
+
+    #[test]
+    fn overhead_pass_curve() {
+        let altitude: f64 = 550.0e3;
+        let orbital_speed: f64 = calculate_circular_orbit_speed(MASS_OF_EARTH, RADIUS_OF_EARTH + altitude);
+
+        let curve = super::doppler_curve(12.0e9, orbital_speed, altitude, RADIUS_OF_EARTH, 101);
+
+        assert_eq!(101, curve.len());
+        assert_eq!(0.0, curve[0].0);
+
+        let (_, first) = curve[0];
+        let (_, middle) = curve[50];
+        let (_, last) = curve[100];
+
+        // approaching, overhead, receding
+        assert!(first > 0.0);
+        assert!(middle.abs() < 1e-6);
+        assert!(last < 0.0);
+
+        // antisymmetric about closest approach
+        assert!((first + last).abs() < 1e-6);
+    }
+
+    #[test]
+    fn two_way_is_double_one_way() {
+        // 7.5 km/s at 2.2 GHz S-band
+        let one_way: f64 = super::doppler_shift_hz(2.2e9, 7.5e3);
+        let two_way: f64 = super::doppler_two_way_shift_hz(2.2e9, 7.5e3);
+
+        // the (v/c)^2 term is ~1e-9 of the shift
+        assert!((two_way / one_way - 2.0).abs() < 1e-4);
+        assert_eq!(2.2e9 + two_way, super::doppler_two_way_received_frequency(2.2e9, 7.5e3));
+    }
+
+    #[test]
+    fn relativistic_at_leo_speed() {
+        let classical: f64 = super::doppler_shift_hz(28.0e9, 7.5e3);
+        let relativistic: f64 = super::doppler_shift_relativistic_hz(28.0e9, 7.5e3);
+
+        // ~700 kHz shift, the two agree to better than a part in 10^4
+        assert!((relativistic - classical).abs() / classical < 1e-4);
+    }
+
+    #[test]
+    fn relativistic_at_a_tenth_of_c() {
+        let velocity: f64 = 0.1 * crate::constants::SPEED_OF_LIGHT;
+        let classical: f64 = super::doppler_shift_hz(1.0e9, velocity);
+        let relativistic: f64 = super::doppler_shift_relativistic_hz(1.0e9, velocity);
+
+        // sqrt(1.1 / 0.9) - 1 = 0.1055 against 0.1
+        assert!((relativistic - 105.5e6).abs() < 0.1e6);
+        assert!((relativistic - classical) / classical > 0.05);
+    }
+
+    #[test]
+    fn leo_max_doppler_ku_band() {
+        // 550 km at 12 GHz, ~7.6 km/s with ~7 km/s along the line of sight at the horizon
+        let shift: f64 = super::leo_max_doppler_hz(12.0e9, 550.0e3, MASS_OF_EARTH, RADIUS_OF_EARTH);
+
+        assert!(shift > 250.0e3 && shift < 320.0e3);
+
+        // the largest sample of an overhead pass curve
+        let orbit_radius: f64 = RADIUS_OF_EARTH + 550.0e3;
+        let speed: f64 = calculate_circular_orbit_speed(MASS_OF_EARTH, orbit_radius);
+        let curve = super::doppler_curve(12.0e9, speed, 550.0e3, RADIUS_OF_EARTH, 101);
+        let peak: f64 = curve.iter().map(|(_, shift)| shift.abs()).fold(0.0, f64::max);
+        assert!((peak - shift).abs() / shift < 1e-9);
+    }
+
+    #[test]
+    fn precompensation_mirrors_doppler_curve() {
+        let altitude: f64 = 550.0e3;
+        let orbital_speed: f64 = calculate_circular_orbit_speed(MASS_OF_EARTH, RADIUS_OF_EARTH + altitude);
+
+        let curve = super::doppler_curve(12.0e9, orbital_speed, altitude, RADIUS_OF_EARTH, 101);
+        let schedule = super::precompensation_schedule(12.0e9, orbital_speed, altitude, RADIUS_OF_EARTH, 101);
+
+        assert_eq!(curve.len(), schedule.len());
+        for ((time, shift), (tune_time, tune)) in curve.iter().zip(schedule.iter()) {
+            assert_eq!(time, tune_time);
+            assert!((tune + shift - 12.0e9).abs() < 1e-3);
+        }
+
+        // tune low while approaching, high while receding
+        assert!(schedule[0].1 < 12.0e9);
+        assert!((schedule[50].1 - 12.0e9).abs() < 1e-3);
+        assert!(schedule[100].1 > 12.0e9);
+
+        // the satellite sees the nominal frequency throughout
+        for ((_, shift), (_, tune)) in curve.iter().zip(schedule.iter()) {
+            let radial_velocity: f64 = shift / 12.0e9 * SPEED_OF_LIGHT;
+            let received: f64 = super::doppler_received_frequency(*tune, radial_velocity);
+
+            assert!((received - 12.0e9).abs() < 10.0);
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use crate::modulation::Modulation;
+
+// Conversions between SNR, C/No, Es/No, and Eb/No
+// All ratios are in dB, C/No is in dB-Hz, rates are in symbols/s or bits/s
+
+pub fn snr_to_c_over_no(snr_db: f64, bandwidth_hz: f64) -> f64 {
+    // C/No = C/N + 10*log10(B)
+    snr_db + 10.0 * bandwidth_hz.log10()
+}
+
+pub fn c_over_no_to_snr(c_over_no_db_hz: f64, bandwidth_hz: f64) -> f64 {
+    c_over_no_db_hz - 10.0 * bandwidth_hz.log10()
+}
+
+pub fn c_over_no_to_eb_over_no(c_over_no_db_hz: f64, bit_rate_bps: f64) -> f64 {
+    // Eb/No = C/No - 10*log10(Rb)
+    c_over_no_db_hz - 10.0 * bit_rate_bps.log10()
+}
+
+pub fn eb_over_no_to_c_over_no(eb_over_no_db: f64, bit_rate_bps: f64) -> f64 {
+    eb_over_no_db + 10.0 * bit_rate_bps.log10()
+}
+
+pub fn c_over_no_to_es_over_no(c_over_no_db_hz: f64, symbol_rate: f64) -> f64 {
+    // Es/No = C/No - 10*log10(Rs)
+    c_over_no_db_hz - 10.0 * symbol_rate.log10()
+}
+
+pub fn es_over_no_to_c_over_no(es_over_no_db: f64, symbol_rate: f64) -> f64 {
+    es_over_no_db + 10.0 * symbol_rate.log10()
+}
+
+pub fn es_over_no_to_eb_over_no(es_over_no_db: f64, modulation: &Modulation) -> f64 {
+    // Es = k * Eb
+    es_over_no_db - 10.0 * (modulation.bits_per_symbol() as f64).log10()
+}
+
+pub fn eb_over_no_to_es_over_no(eb_over_no_db: f64, modulation: &Modulation) -> f64 {
+    eb_over_no_db + 10.0 * (modulation.bits_per_symbol() as f64).log10()
+}
+
+pub fn snr_to_eb_over_no(snr_db: f64, bandwidth_hz: f64, modulation: &Modulation, symbol_rate: f64) -> f64 {
+    // SNR (in the noise bandwidth) -> C/No -> Es/No -> Eb/No
+    let c_over_no: f64 = snr_to_c_over_no(snr_db, bandwidth_hz);
+    let es_over_no: f64 = c_over_no_to_es_over_no(c_over_no, symbol_rate);
+
+    es_over_no_to_eb_over_no(es_over_no, modulation)
+}
+
+pub fn snr_to_eb_over_no_simple(snr_db: f64, bandwidth_hz: f64, info_bit_rate_bps: f64) -> f64 {
+    // SNR -> C/No -> Eb/No through the information bit rate, no modulation needed
+    c_over_no_to_eb_over_no(snr_to_c_over_no(snr_db, bandwidth_hz), info_bit_rate_bps)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::modulation::Modulation;
+
+    #[test]
+    fn snr_to_c_over_no() {
+        let c_over_no: f64 = super::snr_to_c_over_no(10.0, 1.0e6);
+
+        assert_eq!(70.0, c_over_no);
+        assert_eq!(10.0, super::c_over_no_to_snr(c_over_no, 1.0e6));
+    }
+
+    #[test]
+    fn c_over_no_to_eb_over_no() {
+        let eb_over_no: f64 = super::c_over_no_to_eb_over_no(80.0, 10.0e6);
+
+        assert_eq!(10.0, eb_over_no);
+        assert_eq!(80.0, super::eb_over_no_to_c_over_no(eb_over_no, 10.0e6));
+    }
+
+    #[test]
+    fn es_over_no_to_eb_over_no() {
+        // 16-QAM carries 4 bits per symbol, ~6.02 dB
+        let eb_over_no: f64 = super::es_over_no_to_eb_over_no(16.0, &Modulation::Mqam(16));
+
+        assert_eq!(9.979400086720375, eb_over_no);
+    }
+
+    #[test]
+    fn snr_to_eb_over_no() {
+        // symbol rate equal to the noise bandwidth: Es/No == SNR
+        let eb_over_no: f64 = super::snr_to_eb_over_no(10.0, 1.0e6, &Modulation::Qpsk, 1.0e6);
+
+        assert_eq!(6.9897000433601875, eb_over_no);
+    }
+
+    #[test]
+    fn snr_to_eb_over_no_simple() {
+        // 16-QAM at 5 Msps carries 20 Mbps, both paths give the same Eb/No
+        let full: f64 = super::snr_to_eb_over_no(15.0, 6.0e6, &Modulation::Mqam(16), 5.0e6);
+        let simple: f64 = super::snr_to_eb_over_no_simple(15.0, 6.0e6, 20.0e6);
+
+        assert!((full - simple).abs() < 1e-12);
+    }
+}
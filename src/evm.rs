@@ -0,0 +1,136 @@
+// Error vector magnitude (EVM) relations
+// EVM is RMS and normalized to the average constellation power, so SNR = 1 / EVM^2
+
+use crate::ber;
+use crate::energy;
+use crate::modulation::Modulation;
+
+pub fn evm_percent_to_snr_db(evm_percent: f64) -> f64 {
+    let evm: f64 = evm_percent / 100.0;
+    -20.0 * evm.log10()
+}
+
+pub fn snr_db_to_evm_percent(snr_db: f64) -> f64 {
+    100.0 * 10.0_f64.powf(-snr_db / 20.0)
+}
+
+// evm_margin with the SNRs behind it, for reports
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct EvmResult {
+    pub pass: bool,
+    pub margin_db: f64,
+    pub measured_snr_db: f64,
+    pub required_snr_db: f64,
+}
+
+pub fn evm_margin(measured_evm_percent: f64, required_evm_percent: f64) -> (bool, f64) {
+    // returns (pass, margin in dB), a lower EVM than required is positive margin
+    let result: EvmResult = evm_margin_detailed(measured_evm_percent, required_evm_percent);
+
+    (result.pass, result.margin_db)
+}
+
+pub fn evm_margin_detailed(measured_evm_percent: f64, required_evm_percent: f64) -> EvmResult {
+    let measured_snr_db: f64 = evm_percent_to_snr_db(measured_evm_percent);
+    let required_snr_db: f64 = evm_percent_to_snr_db(required_evm_percent);
+    let margin_db: f64 = measured_snr_db - required_snr_db;
+
+    EvmResult {
+        pass: margin_db >= 0.0,
+        margin_db,
+        measured_snr_db,
+        required_snr_db,
+    }
+}
+
+pub fn required_evm_percent(modulation: &Modulation, target_ber: f64) -> Option<f64> {
+    // largest EVM that still meets target_ber, treating the error vector as the only noise
+    // SNR is taken per symbol, Es/No = Eb/No + 10*log10(k)
+    let eb_no_db: f64 = ber::required_eb_no_db(target_ber, modulation)?;
+    let snr_db: f64 = energy::eb_over_no_to_es_over_no(eb_no_db, modulation);
+
+    Some(snr_db_to_evm_percent(snr_db))
+}
+
+pub fn ber_from_evm(evm_percent: f64, modulation: &Modulation) -> f64 {
+    // inverse of required_evm_percent, EVM -> Es/No -> Eb/No -> BER for the constellation
+    let snr_db: f64 = evm_percent_to_snr_db(evm_percent);
+    let eb_no_db: f64 = energy::es_over_no_to_eb_over_no(snr_db, modulation);
+
+    ber::ber_from_db(eb_no_db, modulation)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::modulation::Modulation;
+
+    #[test]
+    fn ten_percent() {
+        assert_eq!(20.0, super::evm_percent_to_snr_db(10.0));
+        assert_eq!(10.0, super::snr_db_to_evm_percent(20.0));
+    }
+
+    #[test]
+    fn evm_margin() {
+        let (pass, margin_db) = super::evm_margin(5.0, 10.0);
+
+        assert!(pass);
+        assert_eq!(6.020599913279625, margin_db);
+
+        let (pass, _) = super::evm_margin(12.0, 10.0);
+        assert!(!pass);
+    }
+
+    #[test]
+    fn evm_margin_detailed() {
+        let result = super::evm_margin_detailed(5.0, 10.0);
+
+        assert_eq!(result.measured_snr_db - result.required_snr_db, result.margin_db);
+        assert_eq!(20.0, result.required_snr_db);
+        assert_eq!(super::evm_margin(5.0, 10.0), (result.pass, result.margin_db));
+
+        let result = super::evm_margin_detailed(12.0, 10.0);
+        assert!(!result.pass);
+        assert!(result.measured_snr_db < result.required_snr_db);
+    }
+
+    #[test]
+    fn required_evm_tightens_with_order() {
+        let qpsk: f64 = super::required_evm_percent(&Modulation::Qpsk, 1e-6).unwrap();
+        let qam16: f64 = super::required_evm_percent(&Modulation::Mqam(16), 1e-6).unwrap();
+        let qam64: f64 = super::required_evm_percent(&Modulation::Mqam(64), 1e-6).unwrap();
+        let qam256: f64 = super::required_evm_percent(&Modulation::Mqam(256), 1e-6).unwrap();
+
+        assert!(qpsk > qam16);
+        assert!(qam16 > qam64);
+        assert!(qam64 > qam256);
+
+        // 256-QAM needs roughly 2% or better
+        assert!(qam256 > 1.0 && qam256 < 3.0);
+    }
+
+    #[test]
+    fn required_evm_unreachable() {
+        assert_eq!(None, super::required_evm_percent(&Modulation::Qpsk, 0.6));
+    }
+
+    #[test]
+    fn ber_from_evm_depends_on_constellation() {
+        // 8% EVM (~22 dB SNR) is clean for QPSK but marginal for 64-QAM
+        let qpsk: f64 = super::ber_from_evm(8.0, &Modulation::Qpsk);
+        let qam64: f64 = super::ber_from_evm(8.0, &Modulation::Mqam(64));
+
+        assert!(qpsk < 1e-20);
+        assert!(qam64 > 1e-5);
+        assert!(qam64 / qpsk > 1e10);
+    }
+
+    #[test]
+    fn ber_from_required_evm() {
+        let evm: f64 = super::required_evm_percent(&Modulation::Mqam(16), 1e-6).unwrap();
+        let ber: f64 = super::ber_from_evm(evm, &Modulation::Mqam(16));
+
+        assert!((ber / 1e-6 - 1.0).abs() < 1e-3);
+    }
+}
@@ -18,6 +18,48 @@ pub fn calculate_slant_range(elevation_angle_degrees: f64, altitude: f64, body_r
     body_radius * (inner_term - f64::sin(elevation_angle_radians))
 }
 
+pub fn slant_range_from_central_angle(altitude: f64, body_radius: f64, central_angle_degrees: f64) -> f64 {
+    // law of cosines in the triangle (body center, ground station, satellite)
+    // central_angle_degrees is the Earth central angle between the station and the subsatellite point
+    let central_angle_radians: f64 = crate::conversions::angle::degrees_to_radians(central_angle_degrees);
+    let total_radius: f64 = altitude + body_radius;
+
+    f64::sqrt(
+        body_radius * body_radius + total_radius * total_radius
+            - 2.0 * body_radius * total_radius * f64::cos(central_angle_radians),
+    )
+}
+
+pub fn elevation_angle_from_central_angle(altitude: f64, body_radius: f64, central_angle_degrees: f64) -> f64 {
+    // tan(el) = (r * cos(gamma) - R) / (r * sin(gamma)), negative below the horizon
+    let central_angle_radians: f64 = crate::conversions::angle::degrees_to_radians(central_angle_degrees);
+    let total_radius: f64 = altitude + body_radius;
+
+    f64::atan2(
+        total_radius * f64::cos(central_angle_radians) - body_radius,
+        total_radius * f64::sin(central_angle_radians),
+    )
+    .to_degrees()
+}
+
+pub fn max_central_angle_deg(altitude: f64, body_radius: f64, min_elevation_degrees: f64) -> f64 {
+    // gamma = 90 - el - nadir angle, with sin(nadir) = R * cos(el) / (R + h)
+    // 0 at a 90 degree minimum elevation, the horizon limit at 0 degrees
+    let min_elevation_radians: f64 = crate::conversions::angle::degrees_to_radians(min_elevation_degrees);
+    let total_radius: f64 = altitude + body_radius;
+
+    let nadir_angle_degrees: f64 = f64::asin(body_radius * f64::cos(min_elevation_radians) / total_radius).to_degrees();
+
+    (90.0 - min_elevation_degrees - nadir_angle_degrees).max(0.0)
+}
+
+pub fn coverage_radius_m(altitude: f64, body_radius: f64, min_elevation_degrees: f64) -> f64 {
+    // footprint radius measured along the surface (arc length)
+    let central_angle_degrees: f64 = max_central_angle_deg(altitude, body_radius, min_elevation_degrees);
+
+    body_radius * crate::conversions::angle::degrees_to_radians(central_angle_degrees)
+}
+
 pub fn calculate_free_space_path_loss(frequency: f64, distance: f64) -> f64 {
     let wavelength: f64 = frequency_to_wavelength(frequency);
     let distance_wavelength_ratio: f64 = distance / wavelength;
@@ -83,6 +125,59 @@ mod tests {
         assert_eq!(3.707020366817534 * altitude, slant_range);
     }
 
+    #[test]
+    fn central_angle_round_trip() {
+        let altitude: f64 = 1.0e6;
+
+        for central_angle_degrees in [0.5, 5.0, 10.0, 20.0] {
+            let elevation_angle_degrees: f64 =
+                crate::fspl::elevation_angle_from_central_angle(altitude, crate::constants::RADIUS_OF_EARTH, central_angle_degrees);
+
+            let from_central_angle: f64 =
+                crate::fspl::slant_range_from_central_angle(altitude, crate::constants::RADIUS_OF_EARTH, central_angle_degrees);
+            let from_elevation: f64 = crate::fspl::SlantRange {
+                elevation_angle_degrees,
+                altitude,
+            }
+            .calculate();
+
+            assert!((from_central_angle - from_elevation).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn zero_central_angle_is_overhead() {
+        let altitude: f64 = 1.0e6;
+
+        assert_eq!(altitude, crate::fspl::slant_range_from_central_angle(altitude, 6371000.0, 0.0));
+        assert_eq!(90.0, crate::fspl::elevation_angle_from_central_angle(altitude, 6371000.0, 0.0));
+    }
+
+    #[test]
+    fn leo_coverage_radius() {
+        // 550 km LEO above 10 degrees, ~15 degrees central angle, ~1660 km footprint
+        let radius: f64 = crate::fspl::coverage_radius_m(550.0e3, 6371000.0, 10.0);
+
+        assert!(radius > 1.6e6 && radius < 1.7e6);
+    }
+
+    #[test]
+    fn max_central_angle_limits() {
+        let altitude: f64 = 550.0e3;
+
+        assert!(crate::fspl::max_central_angle_deg(altitude, 6371000.0, 90.0).abs() < 1e-12);
+
+        // at the horizon the line of sight is tangent, cos(gamma) = R / (R + h)
+        let horizon: f64 = crate::fspl::max_central_angle_deg(altitude, 6371000.0, 0.0);
+        let expected: f64 = (6371000.0_f64 / (6371000.0 + altitude)).acos().to_degrees();
+        assert!((horizon - expected).abs() < 1e-9);
+
+        // the edge of coverage is exactly at the minimum elevation
+        let edge: f64 = crate::fspl::max_central_angle_deg(altitude, 6371000.0, 10.0);
+        let elevation: f64 = crate::fspl::elevation_angle_from_central_angle(altitude, 6371000.0, edge);
+        assert!((elevation - 10.0).abs() < 1e-9);
+    }
+
     use crate::fspl::calculate_free_space_path_loss;
 
     #[test]
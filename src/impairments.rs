@@ -0,0 +1,324 @@
+// Modem impairments expressed as Eb/No penalties
+// The results feed the implementation_loss_db parameter in sensitivity
+
+pub fn phase_noise_snr_floor_db(integrated_phase_noise_rad_rms: f64) -> f64 {
+    // small-angle approximation, e^(j*phi) ~ 1 + j*phi, so the phase error is additive
+    // quadrature noise with power sigma^2 relative to the carrier: SNR <= 1 / sigma^2
+    -20.0 * integrated_phase_noise_rad_rms.log10()
+}
+
+pub fn phase_noise_snr_degradation_db(integrated_phase_noise_rad_rms: f64) -> f64 {
+    // coherent signal power lost to Gaussian phase jitter, |E[e^(j*phi)]|^2 = exp(-sigma^2)
+    // ~4.34 * sigma^2 dB, valid while sigma is small (a few degrees)
+    let variance: f64 = integrated_phase_noise_rad_rms * integrated_phase_noise_rad_rms;
+
+    10.0 * std::f64::consts::LOG10_E * variance
+}
+
+pub fn implementation_loss_from_evm_db(evm_percent: f64, es_no_db: f64) -> f64 {
+    // residual EVM is noise-like error power at EVM^2 of the signal power (SNR = 1 / EVM^2)
+    // Added to thermal noise N at the operating Es/No = S/N, the noise grows to
+    // N + EVM^2 * S, so the loss is L = 10*log10(1 + Es/No * EVM^2): the cleaner the link,
+    // the more a fixed EVM costs (e.g. 10% EVM at 10 dB Es/No is 0.41 dB)
+    let evm: f64 = evm_percent / 100.0;
+    let es_no: f64 = 10.0_f64.powf(es_no_db / 10.0);
+
+    10.0 * (1.0 + es_no * evm * evm).log10()
+}
+
+pub fn dispersion_snr_penalty_db(symbol_rate_hz: f64, group_delay_slope_s_per_hz: f64, es_no_db: f64) -> f64 {
+    // linear group delay across the symbol bandwidth spreads each symbol by
+    // dtau = slope * Rs, a fraction dtau * Rs = slope * Rs^2 of the symbol period
+    // The smeared energy is treated as an error vector of that fraction, which holds
+    // while the spread is small (under ~0.3 of a symbol), beyond that an equalizer is needed
+    // Like any EVM the loss depends on the operating Es/No
+    let spread: f64 = group_delay_slope_s_per_hz.abs() * symbol_rate_hz * symbol_rate_hz;
+
+    implementation_loss_from_evm_db(100.0 * spread, es_no_db)
+}
+
+// offsets past half a subcarrier (or symbol rate) land on the neighbouring one
+const MAXIMUM_NORMALIZED_CFO: f64 = 0.5;
+
+pub fn cfo_snr_penalty_db(normalized_cfo: f64, es_no_db: f64) -> f64 {
+    // normalized_cfo is offset / subcarrier spacing (OFDM) or offset * symbol period (single carrier)
+    // The residual offset leaks (pi * eps)^2 / 3 of the signal power into intercarrier
+    // interference, which adds to the noise at the operating Es/No, giving the small-offset
+    // degradation D ~ 10 / (3 * ln 10) * (pi * eps)^2 * Es/No dB (Pollet et al.)
+    // Only meaningful for small offsets, clamped to half a subcarrier
+    let epsilon: f64 = normalized_cfo.abs().min(MAXIMUM_NORMALIZED_CFO);
+    let es_no: f64 = 10.0_f64.powf(es_no_db / 10.0);
+
+    10.0 / (3.0 * std::f64::consts::LN_10) * (std::f64::consts::PI * epsilon).powi(2) * es_no
+}
+
+fn raised_cosine(frequency: f64, rolloff: f64) -> f64 {
+    // raised-cosine power response at a frequency normalized to the symbol rate, 1 in the passband
+    let f: f64 = frequency.abs();
+    let passband_edge: f64 = (1.0 - rolloff) / 2.0;
+
+    if f <= passband_edge {
+        1.0
+    } else if f <= (1.0 + rolloff) / 2.0 {
+        0.5 * (1.0 + (std::f64::consts::PI / rolloff * (f - passband_edge)).cos())
+    } else {
+        0.0
+    }
+}
+
+fn simpson(integrand: impl Fn(f64) -> f64, start: f64, stop: f64) -> f64 {
+    const INTERVALS: usize = 1000;
+
+    let h: f64 = (stop - start) / INTERVALS as f64;
+    let mut sum: f64 = integrand(start) + integrand(stop);
+    for i in 1..INTERVALS {
+        let weight: f64 = if i % 2 == 1 { 4.0 } else { 2.0 };
+        sum += weight * integrand(start + i as f64 * h);
+    }
+
+    sum * h / 3.0
+}
+
+pub fn aci_c_over_i_db(rolloff: f64, channel_spacing_hz: f64, symbol_rate_hz: f64) -> f64 {
+    // adjacent channel C/I for two equal power carriers with root raised-cosine filtering
+    // at both ends, so each spectrum and the receive filter's power response are raised-cosine.
+    // The neighbour's tail that falls inside this channel's receive filter is the interference:
+    // I = int H(f) * H(f - spacing) df against C = int H(f)^2 df (frequencies in units of Rs)
+    // Carriers spaced at or beyond the occupied bandwidth, Rs * (1 + rolloff), don't overlap
+    let spacing: f64 = channel_spacing_hz / symbol_rate_hz;
+    let edge: f64 = (1.0 + rolloff) / 2.0;
+
+    if spacing >= 2.0 * edge {
+        return f64::INFINITY;
+    }
+
+    let carrier: f64 = simpson(|f| raised_cosine(f, rolloff).powi(2), -edge, edge);
+    let interference: f64 = simpson(
+        |f| raised_cosine(f, rolloff) * raised_cosine(f - spacing, rolloff),
+        spacing - edge,
+        edge,
+    );
+
+    10.0 * (carrier / interference).log10()
+}
+
+// two-tone C/IM3 of a typical TWTA at saturation (dB)
+const TWO_CARRIER_C_OVER_IM3_AT_SATURATION_DB: f64 = 10.0;
+
+pub fn c_over_im3_db(output_backoff_db: f64, num_carriers: u32) -> f64 {
+    // simplified third-order model for equally loaded carriers:
+    // IM3 power grows 3 dB per dB of drive against 1 dB for the carrier, so C/IM3
+    // improves ~2 dB per dB of output backoff from the two-tone saturation value.
+    // More carriers put ~3N^2/8 products on the center carrier, 20*log10(N/2) worse than two.
+    // Combine the result with budget::combine_c_over_i_and_c_over_n
+    if num_carriers < 2 {
+        // a single carrier has no intermodulation
+        return f64::INFINITY;
+    }
+
+    TWO_CARRIER_C_OVER_IM3_AT_SATURATION_DB + 2.0 * output_backoff_db - 20.0 * (num_carriers as f64 / 2.0).log10()
+}
+
+// Degradations applied on top of the thermal SNR by budget::LinkBudget::composite_margin_db
+// Penalties are Eb/No losses in dB (e.g. from the functions above, evaluated at the link's
+// own Es/No since the noise-like ones grow with it), the C/I terms are interference ratios
+// that combine with C/N, None when the source is absent
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Impairments {
+    pub implementation_loss_db: f64,
+    pub phase_noise_penalty_db: f64,
+    pub cfo_penalty_db: f64,
+    pub aci_c_over_i_db: Option<f64>,       // e.g. aci_c_over_i_db
+    pub intermod_c_over_i_db: Option<f64>,  // e.g. c_over_im3_db
+    pub cochannel_c_over_i_db: Option<f64>, // e.g. rain::xpd_from_attenuation_db
+}
+
+impl Impairments {
+    pub fn total_penalty_db(&self) -> f64 {
+        // the dB penalties stack directly
+        self.implementation_loss_db + self.phase_noise_penalty_db + self.cfo_penalty_db
+    }
+
+    pub fn c_over_i_db(&self) -> Option<f64> {
+        // independent interferers add in power, 1/(C/I) = sum of 1/(C/I)_i
+        let terms: Vec<f64> = [self.aci_c_over_i_db, self.intermod_c_over_i_db, self.cochannel_c_over_i_db]
+            .into_iter()
+            .flatten()
+            .collect();
+
+        if terms.is_empty() {
+            return None;
+        }
+
+        let i_over_c: f64 = terms.iter().map(|c_over_i| 10.0_f64.powf(-c_over_i / 10.0)).sum();
+
+        Some(-10.0 * i_over_c.log10())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    #[test]
+    fn one_degree_phase_noise() {
+        let sigma: f64 = 1.0_f64.to_radians();
+
+        // ~35 dB floor and ~0.001 dB of degradation, small but nonzero
+        let floor: f64 = super::phase_noise_snr_floor_db(sigma);
+        assert!((floor - 35.16).abs() < 0.01);
+
+        let degradation: f64 = super::phase_noise_snr_degradation_db(sigma);
+        assert!(degradation > 0.0 && degradation < 0.01);
+    }
+
+    #[test]
+    fn evm_loss_depends_on_operating_snr() {
+        // 10% EVM at 10 dB Es/No: 10*log10(1 + 10 * 0.01) = 0.414 dB
+        let loss: f64 = super::implementation_loss_from_evm_db(10.0, 10.0);
+        assert!((loss - 0.41392685158225073).abs() < 1e-12);
+
+        // the same EVM costs far less on a noisy link, 0.043 dB at 0 dB
+        let noisy: f64 = super::implementation_loss_from_evm_db(10.0, 0.0);
+        assert!((noisy - 0.043213737826425784).abs() < 1e-12);
+        assert!(super::implementation_loss_from_evm_db(10.0, 20.0) > loss);
+    }
+
+    #[test]
+    fn evm_loss_matches_evm_snr_floor() {
+        // with the thermal SNR equal to the EVM SNR the two noise powers are equal, 3 dB
+        let evm_snr_db: f64 = crate::evm::evm_percent_to_snr_db(10.0);
+
+        let loss: f64 = super::implementation_loss_from_evm_db(10.0, evm_snr_db);
+
+        assert!((loss - 10.0 * 2.0_f64.log10()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn phase_noise_as_evm() {
+        // small phase jitter is an error vector of magnitude sigma
+        let sigma: f64 = 2.0_f64.to_radians();
+
+        let from_phase: f64 = super::phase_noise_snr_degradation_db(sigma);
+        // at 0 dB Es/No the EVM loss reduces to 10*log10(1 + sigma^2)
+        let from_evm: f64 = super::implementation_loss_from_evm_db(100.0 * sigma, 0.0);
+
+        assert!((from_phase - from_evm).abs() < 1e-4);
+    }
+
+    #[test]
+    fn dispersion_penalty() {
+        assert_eq!(0.0, super::dispersion_snr_penalty_db(30.0e6, 0.0, 10.0));
+
+        // 0.01 ns/MHz over a 30 Msym/s carrier, 0.3 ns of spread on a 33 ns symbol
+        let small: f64 = super::dispersion_snr_penalty_db(30.0e6, 0.01e-9 / 1.0e6, 10.0);
+        let large: f64 = super::dispersion_snr_penalty_db(30.0e6, 0.1e-9 / 1.0e6, 10.0);
+
+        assert!(small > 0.0 && small < 0.01);
+        assert!(large > small);
+    }
+
+    #[test]
+    fn dispersion_penalty_depends_on_operating_snr() {
+        // 0.1 ns/MHz at 30 Msym/s smears 9% of the symbol, an EVM of 9%
+        let slope: f64 = 0.1e-9 / 1.0e6;
+        let penalty: f64 = super::dispersion_snr_penalty_db(30.0e6, slope, 15.0);
+
+        assert!((penalty - super::implementation_loss_from_evm_db(9.0, 15.0)).abs() < 1e-12);
+        assert!(super::dispersion_snr_penalty_db(30.0e6, slope, 25.0) > penalty);
+    }
+
+    #[test]
+    fn c_over_im3_backoff() {
+        let one_db: f64 = super::c_over_im3_db(1.0, 4);
+        let three_db: f64 = super::c_over_im3_db(3.0, 4);
+
+        assert!((three_db - one_db - 4.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn c_over_im3_carriers() {
+        assert_eq!(16.0, super::c_over_im3_db(3.0, 2));
+        assert!(super::c_over_im3_db(3.0, 2) > super::c_over_im3_db(3.0, 8));
+        assert_eq!(f64::INFINITY, super::c_over_im3_db(3.0, 1));
+    }
+
+    #[test]
+    fn cfo_snr_penalty_zero() {
+        assert_eq!(0.0, super::cfo_snr_penalty_db(0.0, 15.0));
+    }
+
+    #[test]
+    fn cfo_snr_penalty_small() {
+        // 5% of the subcarrier spacing at 15 dB Es/No: 10 / (3 ln 10) * (0.05 pi)^2 * 31.6 ~ 1.13 dB
+        let penalty: f64 = super::cfo_snr_penalty_db(0.05, 15.0);
+
+        assert!((penalty - 1.129543109583902).abs() < 1e-9);
+        assert_eq!(penalty, super::cfo_snr_penalty_db(-0.05, 15.0));
+    }
+
+    #[test]
+    fn cfo_snr_penalty_grows_with_es_no() {
+        // linear in Es/No, 10 dB more SNR is ten times the penalty
+        let low: f64 = super::cfo_snr_penalty_db(0.02, 5.0);
+        let high: f64 = super::cfo_snr_penalty_db(0.02, 15.0);
+
+        assert!((high / low - 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn cfo_snr_penalty_clamped() {
+        let half: f64 = super::cfo_snr_penalty_db(0.5, 10.0);
+
+        assert_eq!(half, super::cfo_snr_penalty_db(3.0, 10.0));
+    }
+
+    #[test]
+    fn aci_no_overlap() {
+        // 0.2 roll-off occupies 1.2 * Rs
+        assert_eq!(f64::INFINITY, super::aci_c_over_i_db(0.2, 1.2e6, 1.0e6));
+        assert_eq!(f64::INFINITY, super::aci_c_over_i_db(0.2, 1.5e6, 1.0e6));
+    }
+
+    #[test]
+    fn aci_wider_spacing_improves() {
+        let tight: f64 = super::aci_c_over_i_db(0.35, 1.05e6, 1.0e6);
+        let wide: f64 = super::aci_c_over_i_db(0.35, 1.2e6, 1.0e6);
+
+        assert!(wide > tight);
+        // only the roll-off tails overlap, the interference stays well below the carrier
+        assert!(tight > 10.0);
+    }
+
+    #[test]
+    fn aci_tighter_rolloff_improves() {
+        let tight: f64 = super::aci_c_over_i_db(0.1, 1.05e6, 1.0e6);
+        let loose: f64 = super::aci_c_over_i_db(0.35, 1.05e6, 1.0e6);
+
+        assert!(tight > loose);
+    }
+
+    #[test]
+    fn aci_overlapping_channels() {
+        // co-channel (no spacing) gives C/I = 0 dB
+        assert!(super::aci_c_over_i_db(0.2, 0.0, 1.0e6).abs() < 1e-6);
+        assert!(super::aci_c_over_i_db(0.2, 0.5e6, 1.0e6) < 3.0);
+    }
+
+    #[test]
+    fn impairments_combine() {
+        let impairments = super::Impairments {
+            implementation_loss_db: 1.0,
+            phase_noise_penalty_db: 0.2,
+            cfo_penalty_db: 0.1,
+            aci_c_over_i_db: Some(20.0),
+            intermod_c_over_i_db: Some(20.0),
+            cochannel_c_over_i_db: None,
+        };
+
+        assert!((impairments.total_penalty_db() - 1.3).abs() < 1e-12);
+        // two equal interferers, 3 dB worse than either
+        assert!((impairments.c_over_i_db().unwrap() - 16.989700043360187).abs() < 1e-9);
+        assert_eq!(None, super::Impairments::default().c_over_i_db());
+    }
+}
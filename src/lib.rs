@@ -1,8 +1,54 @@
+pub mod antenna;
+pub mod atmosphere;
+pub mod ber;
 pub mod budget;
+#[cfg(feature = "serde")]
+pub mod cli;
+pub mod coding;
 pub mod constants;
 pub mod conversions;
+pub mod curve;
+pub mod doppler;
+pub mod energy;
+pub mod evm;
 pub mod fspl;
+pub mod impairments;
+pub mod modulation;
 pub mod orbits;
+pub mod path_loss;
+pub mod pfd;
 pub mod phy;
+pub mod plot;
+pub mod quantization;
+pub mod rain;
 pub mod receiver;
+pub mod sensitivity;
 pub mod transmitter;
+pub mod utils;
+
+pub use doppler::{doppler_received_frequency, doppler_shift_hz};
+pub use path_loss::PathLoss;
+pub use pfd::{pfd_per_mhz, power_flux_density_dbw_per_m2};
+pub use quantization::{enob_from_snr, quantization_snr_db};
+
+#[cfg(test)]
+mod tests {
+
+    #[test]
+    fn crate_root_exports() {
+        // each re-export is reachable from the crate root
+        assert_eq!(0.0, crate::doppler_shift_hz(12.0e9, 0.0));
+        assert_eq!(12.0e9, crate::doppler_received_frequency(12.0e9, 0.0));
+        assert!(crate::power_flux_density_dbw_per_m2(50.0, 35.786e6) < 0.0);
+        assert!(crate::pfd_per_mhz(50.0, 35.786e6, 36.0e6) < 0.0);
+        assert_eq!(74.0, crate::quantization_snr_db(12));
+        assert_eq!(12.0, crate::enob_from_snr(74.0));
+        assert!(crate::PathLoss {
+            frequency: 28.0e9,
+            distance: 1.0e6,
+            excess_loss_db: None,
+        }
+        .calculate()
+            > 0.0);
+    }
+}
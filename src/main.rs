@@ -0,0 +1,18 @@
+use linkbudget::cli::Command;
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    let command = Command::from_args(&args).unwrap_or_else(|error| {
+        eprintln!("{}", error);
+        std::process::exit(1);
+    });
+
+    match command.run() {
+        Ok(budget) => println!("{} -> {}", budget.name, command.output_path),
+        Err(error) => {
+            eprintln!("{}", error);
+            std::process::exit(1);
+        }
+    }
+}
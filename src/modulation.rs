@@ -0,0 +1,451 @@
+use core::fmt;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(into = "String", try_from = "String"))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Modulation {
+    Bpsk,
+    Qpsk,
+    Msk,
+    // M must be a power of two, see Modulation::try_mpsk and Modulation::try_mqam
+    // Built directly this isn't checked, and M = 0 panics in bits_per_symbol
+    Mpsk(u32), // M-ary PSK, M is the constellation order
+    Mqam(u32), // M-ary QAM, M is the constellation order
+    Apsk(u32), // DVB-S2 ring APSK, 16 (4+12) or 32 (4+12+16), see Modulation::try_apsk
+}
+
+impl Modulation {
+    pub fn try_mpsk(m: u32) -> Result<Modulation, ModulationError> {
+        if m < 2 || !m.is_power_of_two() {
+            return Err(ModulationError::InvalidOrder(m));
+        }
+        Ok(Modulation::Mpsk(m))
+    }
+
+    pub fn try_mqam(m: u32) -> Result<Modulation, ModulationError> {
+        // square (4, 16, 64, ...) or cross (32, 128, ...) constellations only,
+        // which rules out 2 and the rectangular 8-QAM
+        if !m.is_power_of_two() {
+            return Err(ModulationError::InvalidOrder(m));
+        }
+        if m < 4 || m == 8 {
+            return Err(ModulationError::NonSquareOrCrossQam(m));
+        }
+        Ok(Modulation::Mqam(m))
+    }
+
+    pub fn try_apsk(m: u32) -> Result<Modulation, ModulationError> {
+        // only the DVB-S2 ring layouts have a BER model
+        if m != 16 && m != 32 {
+            return Err(ModulationError::UnsupportedApskOrder(m));
+        }
+        Ok(Modulation::Apsk(m))
+    }
+
+    pub fn order(&self) -> u32 {
+        match self {
+            Modulation::Bpsk => 2,
+            Modulation::Qpsk => 4,
+            Modulation::Msk => 2,
+            Modulation::Mpsk(m) => *m,
+            Modulation::Mqam(m) => *m,
+            Modulation::Apsk(m) => *m,
+        }
+    }
+
+    pub fn bits_per_symbol(&self) -> u32 {
+        // k = log2(M), panics for an order of 0 (use the try_ constructors)
+        self.order().ilog2()
+    }
+
+    pub fn max_info_bit_rate(&self, bandwidth_hz: f64, rolloff: f64, code_rate: f64) -> f64 {
+        // information bits/s that fit in the channel, Rs * k * r
+        max_symbol_rate(bandwidth_hz, rolloff) * self.bits_per_symbol() as f64 * code_rate
+    }
+
+    pub fn min_for_spectral_efficiency(eta: f64) -> Option<Modulation> {
+        // lowest order scheme with bits_per_symbol >= eta (uncoded, symbol rate equal to bandwidth)
+        // e.g. a Shannon capacity estimate of 3.5 bits/s/Hz needs at least 16-QAM
+        [
+            Modulation::Bpsk,
+            Modulation::Qpsk,
+            Modulation::Mpsk(8),
+            Modulation::Mqam(16),
+            Modulation::Mqam(64),
+            Modulation::Mqam(256),
+        ]
+        .into_iter()
+        .find(|modulation| modulation.bits_per_symbol() as f64 >= eta)
+    }
+}
+
+pub fn occupied_bandwidth(symbol_rate: f64, rolloff: f64) -> f64 {
+    // raised-cosine filtered linear modulations, Rs * (1 + rolloff)
+    symbol_rate * (1.0 + rolloff)
+}
+
+pub fn max_symbol_rate(bandwidth_hz: f64, rolloff: f64) -> f64 {
+    // inverse of occupied_bandwidth, the fastest symbol rate an allocation holds
+    bandwidth_hz / (1.0 + rolloff)
+}
+
+pub fn processing_gain_db(chip_rate_hz: f64, info_bit_rate_bps: f64) -> f64 {
+    // direct-sequence spreading gain, 10*log10(Rc / Rb)
+    // despreading lifts the chip-level SNR to Eb/No by this much
+    10.0 * (chip_rate_hz / info_bit_rate_bps).log10()
+}
+
+// GMSK occupied bandwidth, normalized to the bit rate, for 90, 99, 99.9 and 99.99% of the power
+// (Murota and Hirade, 1981). Rows are keyed by 1/BT so MSK (BT = infinity) sits at 0.
+const GMSK_POWER_PERCENTAGES: [f64; 4] = [90.0, 99.0, 99.9, 99.99];
+const GMSK_OCCUPIED_BANDWIDTH: [(f64, [f64; 4]); 4] = [
+    (0.0, [0.78, 1.20, 2.76, 6.00]),
+    (2.0, [0.69, 1.04, 1.33, 2.08]),
+    (4.0, [0.57, 0.86, 1.09, 1.37]),
+    (5.0, [0.52, 0.79, 0.99, 1.22]),
+];
+
+fn interpolate(x: f64, x0: f64, x1: f64, y0: f64, y1: f64) -> f64 {
+    y0 + (y1 - y0) * (x - x0) / (x1 - x0)
+}
+
+fn gmsk_row_bandwidth(row: &[f64; 4], percent_power: f64) -> f64 {
+    // the tabulated percentages are decades apart in the power left outside, 100 - p
+    let outside: f64 = (100.0 - percent_power).log10();
+    let index: usize = GMSK_POWER_PERCENTAGES[1..3]
+        .iter()
+        .filter(|p| percent_power >= **p)
+        .count();
+
+    interpolate(
+        outside,
+        (100.0 - GMSK_POWER_PERCENTAGES[index]).log10(),
+        (100.0 - GMSK_POWER_PERCENTAGES[index + 1]).log10(),
+        row[index],
+        row[index + 1],
+    )
+}
+
+pub fn occupied_bandwidth_gmsk(symbol_rate: f64, bt_product: f64, percent_power: f64) -> Option<f64> {
+    // Hz containing percent_power of a GMSK (binary, so Rs = Rb) signal's power
+    // MSK and GMSK are not raised-cosine, the Rs * (1 + rolloff) rule doesn't apply
+    // Supports BT >= 0.2 and 90-99.99% of the power, interpolating between tabulated values
+    let (first, last) = (GMSK_POWER_PERCENTAGES[0], GMSK_POWER_PERCENTAGES[3]);
+    if bt_product.is_nan() || bt_product < 0.2 || !(first..=last).contains(&percent_power) {
+        return None;
+    }
+
+    let inverse_bt: f64 = 1.0 / bt_product;
+    let (lower, upper) = GMSK_OCCUPIED_BANDWIDTH
+        .windows(2)
+        .map(|pair| (pair[0], pair[1]))
+        .find(|(lower, upper)| inverse_bt >= lower.0 && inverse_bt <= upper.0)?;
+
+    let normalized: f64 = interpolate(
+        inverse_bt,
+        lower.0,
+        upper.0,
+        gmsk_row_bandwidth(&lower.1, percent_power),
+        gmsk_row_bandwidth(&upper.1, percent_power),
+    );
+
+    Some(normalized * symbol_rate)
+}
+
+pub fn occupied_bandwidth_msk(symbol_rate: f64, percent_power: f64) -> Option<f64> {
+    // MSK is the BT = infinity limit of GMSK, 1.2 Rb holds 99% of the power
+    occupied_bandwidth_gmsk(symbol_rate, f64::INFINITY, percent_power)
+}
+
+impl Display for Modulation {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Modulation::Bpsk => write!(f, "BPSK"),
+            Modulation::Qpsk => write!(f, "QPSK"),
+            Modulation::Msk => write!(f, "MSK"),
+            Modulation::Mpsk(m) => write!(f, "{}-PSK", m),
+            Modulation::Mqam(m) => write!(f, "{}-QAM", m),
+            Modulation::Apsk(m) => write!(f, "{}-APSK", m),
+        }
+    }
+}
+
+impl FromStr for Modulation {
+    type Err = ModulationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // case-insensitive, "16-QAM", "16QAM" and "16 qam" are all accepted
+        let name: String = s
+            .chars()
+            .filter(|c| !matches!(c, '-' | '_' | ' '))
+            .collect::<String>()
+            .to_uppercase();
+
+        match name.as_str() {
+            "BPSK" => return Ok(Modulation::Bpsk),
+            "QPSK" => return Ok(Modulation::Qpsk),
+            "MSK" => return Ok(Modulation::Msk),
+            _ => {}
+        }
+
+        // order prefix followed by the family, APSK before PSK since it shares the suffix
+        let (order, family) = if let Some(order) = name.strip_suffix("APSK") {
+            (order, "APSK")
+        } else if let Some(order) = name.strip_suffix("PSK") {
+            (order, "PSK")
+        } else if let Some(order) = name.strip_suffix("QAM") {
+            (order, "QAM")
+        } else {
+            return Err(ModulationError::Unknown(s.to_string()));
+        };
+
+        let order: u32 = order
+            .parse()
+            .map_err(|_| ModulationError::Unknown(s.to_string()))?;
+
+        match family {
+            "APSK" => Modulation::try_apsk(order),
+            "PSK" => Modulation::try_mpsk(order),
+            _ => Modulation::try_mqam(order),
+        }
+    }
+}
+
+// serialized by name, "16-QAM" rather than {"Mqam": 16}
+impl From<Modulation> for String {
+    fn from(modulation: Modulation) -> String {
+        modulation.to_string()
+    }
+}
+
+impl TryFrom<String> for Modulation {
+    type Error = ModulationError;
+
+    fn try_from(name: String) -> Result<Self, Self::Error> {
+        name.parse()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ModulationError {
+    Unknown(String),
+    InvalidOrder(u32),
+    // a power of two, but QAM only models square and cross constellations
+    NonSquareOrCrossQam(u32),
+    // APSK is only modeled for the DVB-S2 16 and 32 point rings
+    UnsupportedApskOrder(u32),
+}
+
+impl Display for ModulationError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            ModulationError::Unknown(name) => write!(f, "unknown modulation '{}'", name),
+            ModulationError::InvalidOrder(order) => {
+                write!(f, "invalid modulation order {}, expected a power of two", order)
+            }
+            ModulationError::NonSquareOrCrossQam(order) => write!(
+                f,
+                "unsupported QAM order {}, expected a square (4, 16, 64, ...) or cross (32, 128, ...) constellation",
+                order
+            ),
+            ModulationError::UnsupportedApskOrder(order) => {
+                write!(f, "unsupported APSK order {}, expected 16 or 32", order)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ModulationError {}
+
+#[cfg(test)]
+mod tests {
+    use super::{Modulation, ModulationError};
+
+    #[test]
+    fn bits_per_symbol() {
+        assert_eq!(1, Modulation::Bpsk.bits_per_symbol());
+        assert_eq!(2, Modulation::Qpsk.bits_per_symbol());
+        assert_eq!(3, Modulation::Mpsk(8).bits_per_symbol());
+        assert_eq!(4, Modulation::Mqam(16).bits_per_symbol());
+        assert_eq!(8, Modulation::Mqam(256).bits_per_symbol());
+        assert_eq!(5, Modulation::Apsk(32).bits_per_symbol());
+    }
+
+    #[test]
+    fn min_for_spectral_efficiency() {
+        assert_eq!(Some(Modulation::Bpsk), Modulation::min_for_spectral_efficiency(0.5));
+        assert_eq!(Some(Modulation::Bpsk), Modulation::min_for_spectral_efficiency(1.0));
+        assert_eq!(Some(Modulation::Qpsk), Modulation::min_for_spectral_efficiency(2.0));
+        assert_eq!(Some(Modulation::Mpsk(8)), Modulation::min_for_spectral_efficiency(2.1));
+        assert_eq!(Some(Modulation::Mqam(16)), Modulation::min_for_spectral_efficiency(3.5));
+        assert_eq!(Some(Modulation::Mqam(256)), Modulation::min_for_spectral_efficiency(8.0));
+        assert_eq!(None, Modulation::min_for_spectral_efficiency(8.1));
+    }
+
+    #[test]
+    fn max_symbol_rate() {
+        // 36 MHz transponder with a 0.2 roll-off
+        let symbol_rate: f64 = super::max_symbol_rate(36.0e6, 0.2);
+
+        assert_eq!(30.0e6, symbol_rate);
+        assert_eq!(36.0e6, super::occupied_bandwidth(symbol_rate, 0.2));
+    }
+
+    #[test]
+    fn max_info_bit_rate() {
+        // 30 Msps of 8-PSK rate 2/3 is 60 Mbps
+        let bit_rate: f64 = Modulation::Mpsk(8).max_info_bit_rate(36.0e6, 0.2, 2.0 / 3.0);
+
+        assert!((bit_rate - 60.0e6).abs() < 1e-6);
+        assert_eq!(30.0e6, Modulation::Bpsk.max_info_bit_rate(36.0e6, 0.2, 1.0));
+    }
+
+    #[test]
+    fn processing_gain_db() {
+        // a 1023 chip code per bit, ~30 dB
+        let gain: f64 = super::processing_gain_db(1.023e6, 1.0e3);
+
+        assert!((gain - 30.1).abs() < 0.01);
+
+        // GPS C/A: 1.023 Mcps carrying 50 bps
+        assert!((super::processing_gain_db(1.023e6, 50.0) - 43.1).abs() < 0.01);
+    }
+
+    #[test]
+    fn occupied_bandwidth_msk() {
+        assert_eq!(Some(1.2e6), super::occupied_bandwidth_msk(1.0e6, 99.0));
+        assert_eq!(None, super::occupied_bandwidth_msk(1.0e6, 99.999));
+    }
+
+    #[test]
+    fn occupied_bandwidth_gmsk() {
+        // published 99% bandwidths, 0.86 Rb at BT = 0.25 and 0.79 Rb at BT = 0.2
+        let bt_025: f64 = super::occupied_bandwidth_gmsk(1.0e6, 0.25, 99.0).unwrap();
+        let bt_02: f64 = super::occupied_bandwidth_gmsk(1.0e6, 0.2, 99.0).unwrap();
+
+        assert!((bt_025 - 0.86e6).abs() < 1.0);
+        assert!((bt_02 - 0.79e6).abs() < 1.0);
+
+        // GSM's BT = 0.3 falls between the 0.25 and 0.5 rows
+        let gsm: f64 = super::occupied_bandwidth_gmsk(270.833e3, 0.3, 99.0).unwrap();
+        assert!(gsm > 0.86 * 270.833e3 && gsm < 1.04 * 270.833e3);
+
+        assert_eq!(None, super::occupied_bandwidth_gmsk(1.0e6, 0.1, 99.0));
+    }
+
+    #[test]
+    fn gmsk_narrower_than_msk() {
+        let msk: f64 = super::occupied_bandwidth_msk(1.0e6, 99.0).unwrap();
+
+        let mut previous: f64 = msk;
+        for bt in [1.0, 0.5, 0.3, 0.25, 0.2] {
+            let gmsk: f64 = super::occupied_bandwidth_gmsk(1.0e6, bt, 99.0).unwrap();
+
+            assert!(gmsk < previous);
+            previous = gmsk;
+        }
+    }
+
+    #[test]
+    fn gmsk_more_power_needs_more_bandwidth() {
+        let ninety: f64 = super::occupied_bandwidth_gmsk(1.0e6, 0.3, 90.0).unwrap();
+        let ninety_five: f64 = super::occupied_bandwidth_gmsk(1.0e6, 0.3, 95.0).unwrap();
+        let ninety_nine: f64 = super::occupied_bandwidth_gmsk(1.0e6, 0.3, 99.0).unwrap();
+
+        assert!(ninety < ninety_five && ninety_five < ninety_nine);
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!("BPSK", Modulation::Bpsk.to_string());
+        assert_eq!("QPSK", Modulation::Qpsk.to_string());
+        assert_eq!("8-PSK", Modulation::Mpsk(8).to_string());
+        assert_eq!("64-QAM", Modulation::Mqam(64).to_string());
+        assert_eq!("16-APSK", Modulation::Apsk(16).to_string());
+    }
+
+    #[test]
+    fn from_str() {
+        assert_eq!(Ok(Modulation::Bpsk), "BPSK".parse());
+        assert_eq!(Ok(Modulation::Qpsk), "qpsk".parse());
+        assert_eq!(Ok(Modulation::Msk), "MSK".parse());
+        assert_eq!(Ok(Modulation::Mpsk(8)), "8-PSK".parse());
+        assert_eq!(Ok(Modulation::Mpsk(8)), "8PSK".parse());
+        assert_eq!(Ok(Modulation::Mqam(16)), "16-QAM".parse());
+        assert_eq!(Ok(Modulation::Mqam(64)), "64QAM".parse());
+        assert_eq!(Ok(Modulation::Mqam(256)), "256qam".parse());
+        assert_eq!(Ok(Modulation::Apsk(32)), "32-APSK".parse());
+    }
+
+    #[test]
+    fn from_str_round_trip() {
+        let modulations = [
+            Modulation::Bpsk,
+            Modulation::Qpsk,
+            Modulation::Msk,
+            Modulation::Mpsk(8),
+            Modulation::Mqam(16),
+            Modulation::Apsk(16),
+        ];
+
+        for modulation in modulations {
+            assert_eq!(Ok(modulation), modulation.to_string().parse());
+        }
+    }
+
+    #[test]
+    fn from_str_errors() {
+        assert_eq!(
+            Err(ModulationError::Unknown("OOK".to_string())),
+            "OOK".parse::<Modulation>()
+        );
+        assert_eq!(
+            Err(ModulationError::Unknown("x-QAM".to_string())),
+            "x-QAM".parse::<Modulation>()
+        );
+        assert_eq!(
+            Err(ModulationError::InvalidOrder(10)),
+            "10-QAM".parse::<Modulation>()
+        );
+        assert_eq!(
+            "invalid modulation order 10, expected a power of two",
+            "10-QAM".parse::<Modulation>().unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn try_mqam() {
+        assert_eq!(Ok(Modulation::Mqam(16)), Modulation::try_mqam(16));
+        assert_eq!(Ok(Modulation::Mqam(32)), Modulation::try_mqam(32));
+        assert_eq!(Err(ModulationError::InvalidOrder(10)), Modulation::try_mqam(10));
+        assert_eq!(Err(ModulationError::NonSquareOrCrossQam(8)), Modulation::try_mqam(8));
+        assert_eq!(Err(ModulationError::NonSquareOrCrossQam(2)), Modulation::try_mqam(2));
+        assert_eq!(Err(ModulationError::InvalidOrder(0)), Modulation::try_mqam(0));
+
+        // 8 is a power of two, the message says what is actually wrong
+        let message: String = Modulation::try_mqam(8).unwrap_err().to_string();
+        assert!(message.contains("square"));
+        assert!(!message.contains("power of two"));
+    }
+
+    #[test]
+    fn try_apsk() {
+        assert_eq!(Ok(Modulation::Apsk(16)), Modulation::try_apsk(16));
+        assert_eq!(Ok(Modulation::Apsk(32)), Modulation::try_apsk(32));
+        assert_eq!(Err(ModulationError::UnsupportedApskOrder(64)), Modulation::try_apsk(64));
+        assert_eq!(Err(ModulationError::UnsupportedApskOrder(8)), "8-APSK".parse::<Modulation>());
+        assert_eq!(
+            "unsupported APSK order 64, expected 16 or 32",
+            Modulation::try_apsk(64).unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn try_mpsk() {
+        assert_eq!(Ok(Modulation::Mpsk(8)), Modulation::try_mpsk(8));
+        assert_eq!(Err(ModulationError::InvalidOrder(6)), Modulation::try_mpsk(6));
+        assert_eq!(Err(ModulationError::InvalidOrder(0)), Modulation::try_mpsk(0));
+    }
+}
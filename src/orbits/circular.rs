@@ -1,4 +1,4 @@
-use crate::constants::GRAVITATIONAL_CONSTANT;
+use crate::orbits::calculate_standard_gravitational_parameter;
 
 pub fn calculate_circular_orbit_speed(mass_of_body: f64, distance_from_center_of_body: f64) -> f64 {
     // F = G*M*m/ r^2 = mv^2/r
@@ -9,7 +9,7 @@ pub fn calculate_circular_orbit_speed(mass_of_body: f64, distance_from_center_of
     // v = sqrt(G*M/r)
 
     let orbital_speed: f64 =
-        (GRAVITATIONAL_CONSTANT * mass_of_body / distance_from_center_of_body).sqrt();
+        (calculate_standard_gravitational_parameter(mass_of_body) / distance_from_center_of_body).sqrt();
 
     // m/s
     orbital_speed
@@ -32,7 +32,7 @@ pub fn calculate_circular_orbit_period(mass_of_body: f64, distance_from_center_o
     // sqrt(s^2) = s
 
 
-    let inner_term: f64 = distance_from_center_of_body.powf(3.0) / (GRAVITATIONAL_CONSTANT * mass_of_body);
+    let inner_term: f64 = distance_from_center_of_body.powf(3.0) / (calculate_standard_gravitational_parameter(mass_of_body));
 
     let orbital_period: f64 = 2.0 * std::f64::consts::PI * inner_term.sqrt();
 
@@ -40,6 +40,15 @@ pub fn calculate_circular_orbit_period(mass_of_body: f64, distance_from_center_o
     orbital_period
 }
 
+pub fn altitude_for_period(mass_of_body: f64, body_radius_m: f64, period_s: f64) -> f64 {
+    // inverse of calculate_circular_orbit_period
+    // r = (G*M * T^2 / (4*pi^2))^(1/3), altitude above the body's surface in meters
+    let two_pi: f64 = 2.0 * std::f64::consts::PI;
+    let radius: f64 = (calculate_standard_gravitational_parameter(mass_of_body) * period_s * period_s / (two_pi * two_pi)).cbrt();
+
+    radius - body_radius_m
+}
+
 #[cfg(test)]
 mod tests {
     use crate::constants::RADIUS_OF_EARTH;
@@ -92,4 +101,19 @@ mod tests {
         assert_eq!(127.03747979471493, orbital_period_minutes);
     }
 
+    #[test]
+    fn geostationary_altitude() {
+        // one sidereal day, ~35,786 km over the equator
+        // (RADIUS_OF_EARTH is the mean radius, 7 km short of the equatorial radius)
+        let altitude: f64 = super::altitude_for_period(MASS_OF_EARTH, RADIUS_OF_EARTH, 86164.0);
+
+        assert!((altitude - 35_786.0e3).abs() < 10.0e3);
+    }
+
+    #[test]
+    fn altitude_for_period_round_trip() {
+        let altitude: f64 = super::altitude_for_period(MASS_OF_EARTH, RADIUS_OF_EARTH, 6298.058985889903);
+
+        assert!((altitude - 1.0e6).abs() < 1e-3);
+    }
 }
@@ -0,0 +1,99 @@
+use crate::orbits::calculate_standard_gravitational_parameter;
+
+pub struct KeplerianOrbit {
+    pub semi_major_axis_m: f64, // meters, from the center of the body
+    pub eccentricity: f64,      // 0 is circular, 0 < e < 1 is elliptical
+}
+
+impl KeplerianOrbit {
+    pub fn period_s(&self, mass_of_body: f64) -> f64 {
+        // T = 2*pi*sqrt(a^3/G*M)
+        let inner_term: f64 = self.semi_major_axis_m.powf(3.0) / (calculate_standard_gravitational_parameter(mass_of_body));
+
+        2.0 * std::f64::consts::PI * inner_term.sqrt()
+    }
+
+    pub fn apoapsis_m(&self) -> f64 {
+        // farthest distance from the center of the body
+        self.semi_major_axis_m * (1.0 + self.eccentricity)
+    }
+
+    pub fn periapsis_m(&self) -> f64 {
+        // closest distance from the center of the body
+        self.semi_major_axis_m * (1.0 - self.eccentricity)
+    }
+
+    pub fn radius_at_true_anomaly_m(&self, true_anomaly_rad: f64) -> f64 {
+        // r = a(1 - e^2) / (1 + e*cos(nu))
+        self.semi_major_axis_m * (1.0 - self.eccentricity * self.eccentricity)
+            / (1.0 + self.eccentricity * true_anomaly_rad.cos())
+    }
+
+    pub fn velocity_at_true_anomaly_m_s(&self, mass_of_body: f64, true_anomaly_rad: f64) -> f64 {
+        // vis-viva equation
+        // v = sqrt(G*M*(2/r - 1/a))
+        let radius: f64 = self.radius_at_true_anomaly_m(true_anomaly_rad);
+
+        (calculate_standard_gravitational_parameter(mass_of_body) * (2.0 / radius - 1.0 / self.semi_major_axis_m)).sqrt()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::KeplerianOrbit;
+    use crate::constants::MASS_OF_EARTH;
+    use crate::constants::RADIUS_OF_EARTH;
+
+    #[test]
+    fn circular_speed() {
+        let altitude: f64 = 1.0e6;
+        let orbit = KeplerianOrbit {
+            semi_major_axis_m: altitude + RADIUS_OF_EARTH,
+            eccentricity: 0.0,
+        };
+
+        let expected: f64 = crate::orbits::circular::calculate_circular_orbit_speed(
+            MASS_OF_EARTH,
+            altitude + RADIUS_OF_EARTH,
+        );
+
+        // speed is the same everywhere on a circle
+        assert!((orbit.velocity_at_true_anomaly_m_s(MASS_OF_EARTH, 0.0) - expected).abs() < 1e-9);
+        assert!((orbit.velocity_at_true_anomaly_m_s(MASS_OF_EARTH, 2.0) - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn circular_period() {
+        let altitude: f64 = 1.0e6;
+        let orbit = KeplerianOrbit {
+            semi_major_axis_m: altitude + RADIUS_OF_EARTH,
+            eccentricity: 0.0,
+        };
+
+        // seconds, matches the circular orbit
+        assert_eq!(6298.058985889903, orbit.period_s(MASS_OF_EARTH));
+        assert_eq!(orbit.apoapsis_m(), orbit.periapsis_m());
+    }
+
+    #[test]
+    fn molniya() {
+        // ~12 hour Molniya orbit
+        let orbit = KeplerianOrbit {
+            semi_major_axis_m: 26_600.0e3,
+            eccentricity: 0.74,
+        };
+
+        assert_eq!(46_284.0e3, orbit.apoapsis_m());
+        assert!((orbit.periapsis_m() - 6_916.0e3).abs() < 1e-6);
+
+        // fastest at periapsis, slowest at apoapsis
+        let periapsis_speed: f64 = orbit.velocity_at_true_anomaly_m_s(MASS_OF_EARTH, 0.0);
+        let apoapsis_speed: f64 =
+            orbit.velocity_at_true_anomaly_m_s(MASS_OF_EARTH, std::f64::consts::PI);
+        assert!(periapsis_speed > apoapsis_speed);
+
+        // conservation of angular momentum, r_p * v_p == r_a * v_a
+        let ratio: f64 = (orbit.periapsis_m() * periapsis_speed) / (orbit.apoapsis_m() * apoapsis_speed);
+        assert!((ratio - 1.0).abs() < 1e-9);
+    }
+}
@@ -1,7 +1,57 @@
 use crate::constants::GRAVITATIONAL_CONSTANT;
 
 pub mod circular;
+pub mod elliptical;
 
 pub fn calculate_standard_gravitational_parameter(mass_of_bodies: f64) -> f64 {
+    // GM, see constants::MU_EARTH and MU_MOON for the precomputed values
     GRAVITATIONAL_CONSTANT * mass_of_bodies
 }
+
+// apparent motion of the sun across the sky, 360 degrees a day
+const SUN_APPARENT_RATE_DEG_PER_S: f64 = 15.0 / 3600.0;
+
+pub fn sun_outage_duration_s(antenna_beamwidth_deg: f64, sun_angular_diameter_deg: f64) -> f64 {
+    // daily outage of a GEO ground station around the equinoxes, while the sun passes behind
+    // the satellite: from the sun's leading edge entering the half power beam to its trailing
+    // edge leaving, (beamwidth + sun diameter) / (15 degrees per hour)
+    // The sun is ~0.5 degrees across, so even a very narrow beam has an outage
+    (antenna_beamwidth_deg + sun_angular_diameter_deg) / SUN_APPARENT_RATE_DEG_PER_S
+}
+
+#[cfg(test)]
+mod tests {
+
+    #[test]
+    fn sun_outage_duration() {
+        // 1.2 m Ku-band dish, ~1.5 degree beam: 2 degrees at 0.25 degrees per minute
+        let duration: f64 = super::sun_outage_duration_s(1.5, 0.5);
+
+        assert!((duration - 480.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn wider_beam_longer_outage() {
+        let narrow: f64 = super::sun_outage_duration_s(0.3, 0.5);
+        let wide: f64 = super::sun_outage_duration_s(3.0, 0.5);
+
+        assert!(wide > narrow);
+    }
+
+    #[test]
+    fn beam_narrower_than_the_sun() {
+        // the sun's own diameter sets a minimum of two minutes
+        let duration: f64 = super::sun_outage_duration_s(0.1, 0.5);
+
+        assert!(duration > 120.0);
+        assert!((super::sun_outage_duration_s(0.0, 0.5) - 120.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn standard_gravitational_parameter() {
+        use crate::constants::{MASS_OF_EARTH, MASS_OF_MOON, MU_EARTH, MU_MOON};
+
+        assert_eq!(MU_EARTH, super::calculate_standard_gravitational_parameter(MASS_OF_EARTH));
+        assert_eq!(MU_MOON, super::calculate_standard_gravitational_parameter(MASS_OF_MOON));
+    }
+}
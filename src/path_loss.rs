@@ -0,0 +1,266 @@
+use core::fmt;
+use std::f64::consts::PI;
+use std::fmt::{Display, Formatter};
+
+use crate::conversions::frequency::frequency_to_wavelength;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Default)]
+pub struct PathLoss {
+    pub frequency: f64,              // Hz
+    pub distance: f64,               // meters
+    pub excess_loss_db: Option<f64>, // dB, e.g. atmospheric absorption and rain fade
+}
+
+impl PathLoss {
+    pub fn calculate(&self) -> f64 {
+        // free space path loss in dB, unchecked (NaN or -inf for a zero or negative input)
+        crate::fspl::calculate_free_space_path_loss(self.frequency, self.distance)
+    }
+
+    pub fn try_calculate(&self) -> Result<f64, PathLossError> {
+        // free space path loss in dB, after checking the inputs
+        // The far-field formula is meaningless inside one wavelength, where it would
+        // report a loss below 22 dB (or a gain), so that is the minimum distance
+        if !(self.frequency.is_finite() && self.frequency > 0.0) {
+            return Err(PathLossError::InvalidFrequency(self.frequency));
+        }
+
+        let wavelength: f64 = frequency_to_wavelength(self.frequency);
+        if !(self.distance.is_finite() && self.distance >= wavelength) {
+            return Err(PathLossError::InvalidDistance(self.distance));
+        }
+
+        Ok(self.calculate())
+    }
+
+    pub fn total_loss(&self) -> f64 {
+        // free space path loss plus the excess loss, in dB
+        self.calculate() + self.excess_loss_db.unwrap_or(0.0)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PathLossError {
+    InvalidFrequency(f64),
+    InvalidDistance(f64),
+}
+
+impl Display for PathLossError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            PathLossError::InvalidFrequency(frequency) => {
+                write!(f, "invalid frequency {} Hz, expected a positive value", frequency)
+            }
+            PathLossError::InvalidDistance(distance) => {
+                write!(f, "invalid distance {} m, expected at least one wavelength", distance)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PathLossError {}
+
+pub fn fresnel_breakpoint_m(tx_height_m: f64, rx_height_m: f64, frequency_hz: f64) -> f64 {
+    // distance where the ground reflection leaves the first Fresnel zone, 4 * ht * hr / wavelength
+    4.0 * tx_height_m * rx_height_m / frequency_to_wavelength(frequency_hz)
+}
+
+pub fn fresnel_zone_radius_m(zone_number: u32, distance_tx_m: f64, distance_rx_m: f64, frequency_hz: f64) -> f64 {
+    // radius of the nth Fresnel zone at a point distance_tx_m from the transmitter and
+    // distance_rx_m from the receiver, sqrt(n * wavelength * d1 * d2 / (d1 + d2))
+    // The zone closes to a point at either antenna. Keep ~60% of the first zone clear.
+    if distance_tx_m <= 0.0 || distance_rx_m <= 0.0 {
+        return 0.0;
+    }
+
+    let wavelength: f64 = frequency_to_wavelength(frequency_hz);
+
+    (zone_number as f64 * wavelength * distance_tx_m * distance_rx_m / (distance_tx_m + distance_rx_m)).sqrt()
+}
+
+pub fn two_ray_ground_loss_db(tx_height_m: f64, rx_height_m: f64, distance_m: f64, frequency_hz: f64) -> f64 {
+    // exact two-ray model, direct ray plus a ground reflection (coefficient -1, grazing incidence)
+    // beyond the breakpoint the loss trends to 40*log10(d) - 20*log10(ht * hr)
+    let direct: f64 = (distance_m.powi(2) + (tx_height_m - rx_height_m).powi(2)).sqrt();
+
+    if distance_m < fresnel_breakpoint_m(tx_height_m, rx_height_m, frequency_hz) {
+        // the rays only interfere (nulls and peaks) here, use free space
+        return crate::fspl::calculate_free_space_path_loss(frequency_hz, direct);
+    }
+
+    let wavelength: f64 = frequency_to_wavelength(frequency_hz);
+    let reflected: f64 = (distance_m.powi(2) + (tx_height_m + rx_height_m).powi(2)).sqrt();
+    let phase_difference: f64 = 2.0 * PI * (reflected - direct) / wavelength;
+
+    // |1/d1 - exp(-j*phi)/d2|^2
+    let real: f64 = 1.0 / direct - phase_difference.cos() / reflected;
+    let imaginary: f64 = phase_difference.sin() / reflected;
+    let magnitude_squared: f64 = real * real + imaginary * imaginary;
+
+    -10.0 * ((wavelength / (4.0 * PI)).powi(2) * magnitude_squared).log10()
+}
+
+pub fn loss_vs_frequency(distance_m: f64, frequencies_hz: &[f64]) -> Vec<(f64, f64)> {
+    // (frequency_hz, fspl_db) at a fixed distance, e.g. to compare C, Ku and Ka-band
+    frequencies_hz
+        .iter()
+        .map(|&frequency| (frequency, crate::fspl::calculate_free_space_path_loss(frequency, distance_m)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PathLoss, PathLossError};
+    use crate::fspl::calculate_free_space_path_loss;
+
+    #[test]
+    fn leo() {
+        let path_loss = PathLoss {
+            frequency: 28.0e9,
+            distance: 1.0e6,
+            excess_loss_db: None,
+        };
+
+        assert_eq!(181.39094384872777, path_loss.calculate());
+    }
+
+    #[test]
+    fn fresnel_zone_radius_midpoint() {
+        // 10 km link at 2.4 GHz, the planner's 17.32 * sqrt(d_km / (4 * f_GHz)) gives 17.7 m
+        let radius: f64 = super::fresnel_zone_radius_m(1, 5.0e3, 5.0e3, 2.4e9);
+
+        assert!((radius - 17.671).abs() < 1e-3);
+        assert!((super::fresnel_zone_radius_m(2, 5.0e3, 5.0e3, 2.4e9) - radius * 2.0_f64.sqrt()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn fresnel_zone_radius_endpoints() {
+        assert_eq!(0.0, super::fresnel_zone_radius_m(1, 0.0, 10.0e3, 2.4e9));
+        assert_eq!(0.0, super::fresnel_zone_radius_m(1, 10.0e3, 0.0, 2.4e9));
+
+        // widest at the midpoint
+        let near: f64 = super::fresnel_zone_radius_m(1, 1.0e3, 9.0e3, 2.4e9);
+        assert!(near < super::fresnel_zone_radius_m(1, 5.0e3, 5.0e3, 2.4e9));
+    }
+
+    #[test]
+    fn fresnel_breakpoint() {
+        // 2.4 GHz, 10 m antennas, ~3.2 km
+        let breakpoint: f64 = super::fresnel_breakpoint_m(10.0, 10.0, 2.4e9);
+
+        assert_eq!(3202.21531390226, breakpoint);
+    }
+
+    #[test]
+    fn two_ray_short_range_is_free_space() {
+        let loss: f64 = super::two_ray_ground_loss_db(10.0, 10.0, 500.0, 2.4e9);
+
+        assert_eq!(calculate_free_space_path_loss(2.4e9, 500.0), loss);
+    }
+
+    #[test]
+    fn two_ray_long_range() {
+        let distance: f64 = 20.0e3;
+        let two_ray: f64 = super::two_ray_ground_loss_db(10.0, 10.0, distance, 2.4e9);
+        let free_space: f64 = calculate_free_space_path_loss(2.4e9, distance);
+
+        // ground reflection cancels the direct ray far out
+        assert!(two_ray > free_space);
+
+        // 40*log10(d) - 20*log10(ht * hr) plane earth approximation
+        let plane_earth: f64 = 40.0 * distance.log10() - 20.0 * (10.0_f64 * 10.0).log10();
+        assert!((two_ray - plane_earth).abs() < 0.1);
+    }
+
+    #[test]
+    fn two_ray_slope() {
+        // 12 dB per doubling of distance (40 dB/decade) beyond the breakpoint
+        let near: f64 = super::two_ray_ground_loss_db(10.0, 10.0, 20.0e3, 2.4e9);
+        let far: f64 = super::two_ray_ground_loss_db(10.0, 10.0, 40.0e3, 2.4e9);
+
+        assert!((far - near - 12.04).abs() < 0.1);
+    }
+
+    #[test]
+    fn try_calculate() {
+        let path_loss = PathLoss {
+            frequency: 28.0e9,
+            distance: 1.0e6,
+            excess_loss_db: None,
+        };
+
+        assert_eq!(Ok(path_loss.calculate()), path_loss.try_calculate());
+    }
+
+    #[test]
+    fn try_calculate_zero_distance() {
+        let path_loss = PathLoss {
+            frequency: 28.0e9,
+            distance: 0.0,
+            excess_loss_db: None,
+        };
+
+        assert!(path_loss.calculate().is_infinite());
+        assert_eq!(Err(PathLossError::InvalidDistance(0.0)), path_loss.try_calculate());
+    }
+
+    #[test]
+    fn try_calculate_invalid_frequency() {
+        for frequency in [-28.0e9, 0.0, f64::NAN] {
+            let path_loss = PathLoss {
+                frequency,
+                distance: 1.0e6,
+                excess_loss_db: None,
+            };
+
+            assert!(matches!(path_loss.try_calculate(), Err(PathLossError::InvalidFrequency(_))));
+        }
+    }
+
+    #[test]
+    fn try_calculate_inside_a_wavelength() {
+        // ~1.07 cm at 28 GHz
+        let path_loss = PathLoss {
+            frequency: 28.0e9,
+            distance: 0.005,
+            excess_loss_db: None,
+        };
+
+        assert_eq!(
+            "invalid distance 0.005 m, expected at least one wavelength",
+            path_loss.try_calculate().unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn loss_vs_frequency() {
+        // C, Ku and Ka-band over a GEO slant range
+        let frequencies: [f64; 4] = [4.0e9, 8.0e9, 16.0e9, 32.0e9];
+        let losses = super::loss_vs_frequency(38.0e6, &frequencies);
+
+        assert_eq!(frequencies.len(), losses.len());
+        assert_eq!(4.0e9, losses[0].0);
+        assert_eq!(calculate_free_space_path_loss(4.0e9, 38.0e6), losses[0].1);
+
+        // ~6 dB per octave
+        for pair in losses.windows(2) {
+            assert!((pair[1].1 - pair[0].1 - 20.0 * 2.0_f64.log10()).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn total_loss_adds_excess() {
+        let mut path_loss = PathLoss {
+            frequency: 28.0e9,
+            distance: 1.0e6,
+            ..PathLoss::default()
+        };
+
+        assert_eq!(path_loss.calculate(), path_loss.total_loss());
+
+        path_loss.excess_loss_db = Some(4.5);
+        assert_eq!(181.39094384872777, path_loss.calculate());
+        assert_eq!(path_loss.calculate() + 4.5, path_loss.total_loss());
+    }
+}
@@ -0,0 +1,116 @@
+use std::f64::consts::PI;
+
+pub fn power_flux_density_dbw_per_m2(eirp_dbw: f64, distance_m: f64) -> f64 {
+    // PFD = EIRP / (4 * pi * d^2), spreading loss only
+    eirp_dbw - 10.0 * (4.0 * PI * distance_m * distance_m).log10()
+}
+
+pub fn pfd_per_mhz(eirp_dbw: f64, distance_m: f64, bandwidth_hz: f64) -> f64 {
+    // dBW/m^2/MHz, assumes the power is spread evenly across the bandwidth
+    power_flux_density_dbw_per_m2(eirp_dbw, distance_m) - 10.0 * (bandwidth_hz / 1.0e6).log10()
+}
+
+pub fn pfd_per_4khz(eirp_dbw: f64, distance_m: f64, bandwidth_hz: f64) -> f64 {
+    // dBW/m^2 in any 4 kHz, the reference bandwidth of the ITU RR Article 21 limits
+    power_flux_density_dbw_per_m2(eirp_dbw, distance_m) - 10.0 * (bandwidth_hz / 4.0e3).log10()
+}
+
+pub fn epfd_dbw_per_m2(sources: &[(f64, f64, f64)]) -> f64 {
+    // aggregate PFD into a victim receiver, each source is
+    // (eirp_dbw, distance_m, rx_antenna_gain_relative_db) with the gain relative to boresight
+    // contributions add in linear W/m^2, never in dB
+    let total: f64 = sources
+        .iter()
+        .map(|(eirp_dbw, distance_m, gain_relative_db)| {
+            10.0_f64.powf((power_flux_density_dbw_per_m2(*eirp_dbw, *distance_m) + gain_relative_db) / 10.0)
+        })
+        .sum();
+
+    10.0 * total.log10()
+}
+
+pub fn pfd_limit_dbw_per_m2_per_4khz(elevation_deg: f64) -> f64 {
+    // typical Article 21 (Table 21-4) mask: -150 up to 5 degrees,
+    // rising 0.5 dB/degree to -140 at 25 degrees and above
+    if elevation_deg <= 5.0 {
+        -150.0
+    } else if elevation_deg <= 25.0 {
+        -150.0 + (elevation_deg - 5.0) / 2.0
+    } else {
+        -140.0
+    }
+}
+
+pub fn check_pfd_limit(pfd_dbw_per_m2_per_4khz: f64, elevation_deg: f64) -> (bool, f64) {
+    // returns (compliant, margin in dB), positive margin is below the limit
+    let margin_db: f64 = pfd_limit_dbw_per_m2_per_4khz(elevation_deg) - pfd_dbw_per_m2_per_4khz;
+
+    (margin_db >= 0.0, margin_db)
+}
+
+#[cfg(test)]
+mod tests {
+
+    #[test]
+    fn power_flux_density() {
+        // GEO, 50 dBW EIRP
+        let pfd: f64 = super::power_flux_density_dbw_per_m2(50.0, 35.786e6);
+
+        assert_eq!(-112.06636179214453, pfd);
+    }
+
+    #[test]
+    fn pfd_per_mhz() {
+        // 36 MHz transponder spreads the power over 36 one-MHz slices
+        let pfd: f64 = super::power_flux_density_dbw_per_m2(50.0, 35.786e6);
+        let pfd_mhz: f64 = super::pfd_per_mhz(50.0, 35.786e6, 36.0e6);
+
+        assert!((pfd - pfd_mhz - 10.0 * 36.0_f64.log10()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn pfd_per_4khz() {
+        // a 4 kHz carrier has all of its power in the reference bandwidth
+        assert_eq!(
+            super::power_flux_density_dbw_per_m2(50.0, 35.786e6),
+            super::pfd_per_4khz(50.0, 35.786e6, 4.0e3)
+        );
+    }
+
+    #[test]
+    fn pfd_limit_mask() {
+        assert_eq!(-150.0, super::pfd_limit_dbw_per_m2_per_4khz(0.0));
+        assert_eq!(-150.0, super::pfd_limit_dbw_per_m2_per_4khz(5.0));
+        assert_eq!(-145.0, super::pfd_limit_dbw_per_m2_per_4khz(15.0));
+        assert_eq!(-140.0, super::pfd_limit_dbw_per_m2_per_4khz(25.0));
+        assert_eq!(-140.0, super::pfd_limit_dbw_per_m2_per_4khz(90.0));
+    }
+
+    #[test]
+    fn check_pfd_limit() {
+        // -145 is over the limit at the horizon but compliant overhead
+        assert_eq!((false, -5.0), super::check_pfd_limit(-145.0, 3.0));
+        assert_eq!((true, 0.0), super::check_pfd_limit(-145.0, 15.0));
+        assert_eq!((true, 5.0), super::check_pfd_limit(-145.0, 40.0));
+    }
+
+    #[test]
+    fn epfd_single_source() {
+        let epfd: f64 = super::epfd_dbw_per_m2(&[(50.0, 1.0e6, -10.0)]);
+        let expected: f64 = super::power_flux_density_dbw_per_m2(50.0, 1.0e6) - 10.0;
+
+        assert!((epfd - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn epfd_equal_sources() {
+        let single: f64 = super::epfd_dbw_per_m2(&[(30.0, 1.2e6, -3.0)]);
+
+        for n in [2, 10, 64] {
+            let sources: Vec<(f64, f64, f64)> = vec![(30.0, 1.2e6, -3.0); n];
+            let epfd: f64 = super::epfd_dbw_per_m2(&sources);
+
+            assert!((epfd - single - 10.0 * (n as f64).log10()).abs() < 1e-9);
+        }
+    }
+}
@@ -22,6 +22,34 @@ impl PhyRate {
         self.bps() / 1_000_000_000.0
     }
 
+    pub fn snr_linear_for_spectral_efficiency(spectral_efficiency: f64) -> f64 {
+        // Shannon inverted, the SNR needed for eta bits/s/Hz: 2^eta - 1
+        (spectral_efficiency * std::f64::consts::LN_2).exp_m1()
+    }
+
+    pub fn spectral_efficiency(&self) -> f64 {
+        // bits/s/Hz at capacity
+        self.bps() / self.bandwidth
+    }
+
+    pub fn eb_no_db_at_capacity(&self) -> f64 {
+        // Eb/No = SNR / eta when the bit rate equals the capacity
+        // equivalently the Shannon limit at this spectral efficiency
+        shannon_eb_no_limit_db(self.spectral_efficiency())
+    }
+}
+
+pub fn shannon_eb_no_limit_db(spectral_efficiency: f64) -> f64 {
+    // minimum Eb/No (dB) for error-free transmission at eta = R/B bits/s/Hz
+    // Eb/No_min = (2^eta - 1) / eta, tending to ln(2) (-1.59 dB) as eta -> 0
+    if spectral_efficiency <= 0.0 {
+        return 10.0 * std::f64::consts::LN_2.log10();
+    }
+
+    // exp_m1 keeps 2^eta - 1 accurate for small eta
+    let eb_no: f64 = (spectral_efficiency * std::f64::consts::LN_2).exp_m1() / spectral_efficiency;
+
+    10.0 * eb_no.log10()
 }
 
 
@@ -45,4 +73,41 @@ mod tests {
         assert_eq!(phy_rate.mbps(), 80.0);
         assert_eq!(phy_rate.gbps(), 0.08);
     }
+
+    #[test]
+    fn snr_linear_for_spectral_efficiency() {
+        assert!((PhyRate::snr_linear_for_spectral_efficiency(4.0) - 15.0).abs() < 1e-12);
+
+        let phy_rate = PhyRate {
+            bandwidth: 36.0e6,
+            snr: PhyRate::snr_linear_for_spectral_efficiency(2.5),
+        };
+        assert!((phy_rate.bps() - 2.5 * 36.0e6).abs() < 1e-6);
+    }
+
+    #[test]
+    fn spectral_efficiency() {
+        let phy_rate = PhyRate {
+            bandwidth: 20_000_000.0,
+            snr: 15.0,
+        };
+        assert_eq!(phy_rate.spectral_efficiency(), 4.0);
+        // 15 / 4
+        assert!((phy_rate.eb_no_db_at_capacity() - 10.0 * 3.75_f64.log10()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn shannon_limit_low_rate() {
+        // the ultimate Shannon limit, -1.59 dB
+        let limit: f64 = shannon_eb_no_limit_db(1e-9);
+
+        assert!((limit - -1.5917).abs() < 1e-3);
+        assert_eq!(shannon_eb_no_limit_db(0.0), 10.0 * std::f64::consts::LN_2.log10());
+    }
+
+    #[test]
+    fn shannon_limit_one_bit() {
+        // eta = 1: (2 - 1) / 1 = 0 dB
+        assert_eq!(0.0, shannon_eb_no_limit_db(1.0));
+    }
 }
@@ -0,0 +1,271 @@
+use std::fs;
+use std::io::Write;
+
+use crate::budget::LinkBudget;
+use crate::modulation::Modulation;
+use crate::utils::format::{escape_xml, si};
+
+// target BER behind the link margin in the summary
+const SUMMARY_TARGET_BER: f64 = 1e-5;
+
+fn format_distance(meters: f64) -> String {
+    format!("{:.1} km", meters / 1.0e3)
+}
+
+fn format_phy_rate(budget: &LinkBudget) -> String {
+    si(budget.phy_rate().bps(), "bps")
+}
+
+pub fn generate_svg(budget: &LinkBudget, modulation: Option<&Modulation>) -> String {
+    // modem metrics use QPSK unless a modulation is given
+    let modulation: &Modulation = modulation.unwrap_or(&Modulation::Qpsk);
+    let margin: String = match budget.link_margin_db(modulation, SUMMARY_TARGET_BER) {
+        Some(margin) => format!("{:.2} dB", margin),
+        None => "-".to_string(),
+    };
+
+    let frequency: String = si(budget.path_loss.frequency, "Hz");
+    let bandwidth: String = si(budget.bandwidth, "Hz");
+    let distance: String = format_distance(budget.path_loss.distance);
+    let phy_rate: String = format_phy_rate(budget);
+
+    let mut svg: String = String::new();
+    svg.push_str("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"480\" height=\"340\">\n");
+    svg.push_str(&format!(
+        "  <text x=\"10\" y=\"24\" font-size=\"18\" font-weight=\"bold\">{}</text>\n",
+        escape_xml(&budget.name)
+    ));
+
+    svg.push_str("  <g id=\"inputs\" font-size=\"14\">\n");
+    svg.push_str("    <text x=\"10\" y=\"56\" font-weight=\"bold\">Inputs</text>\n");
+    svg.push_str(&format!("    <text x=\"10\" y=\"76\">Frequency: {}</text>\n", frequency));
+    svg.push_str(&format!("    <text x=\"10\" y=\"96\">Bandwidth: {}</text>\n", bandwidth));
+    svg.push_str(&format!("    <text x=\"10\" y=\"116\">Distance: {}</text>\n", distance));
+    svg.push_str(&format!(
+        "    <text x=\"10\" y=\"136\">EIRP: {:.2} dBm</text>\n",
+        budget.transmitter.eirp_dbm()
+    ));
+    svg.push_str(&format!(
+        "    <text x=\"10\" y=\"156\">Path Loss: {:.2} dB</text>\n",
+        budget.path_loss()
+    ));
+    svg.push_str("  </g>\n");
+
+    svg.push_str("  <g id=\"results\" font-size=\"14\">\n");
+    svg.push_str("    <text x=\"10\" y=\"196\" font-weight=\"bold\">Results</text>\n");
+    svg.push_str(&format!("    <text x=\"10\" y=\"216\">SNR: {:.2} dB</text>\n", budget.snr()));
+    svg.push_str(&format!("    <text x=\"10\" y=\"236\">PHY Rate: {}</text>\n", phy_rate));
+    svg.push_str(&format!(
+        "    <text x=\"10\" y=\"256\">Received Power: {:.2} dBm</text>\n",
+        budget.pin_at_receiver()
+    ));
+    svg.push_str(&format!(
+        "    <text x=\"10\" y=\"276\">Eb/No ({}): {:.2} dB</text>\n",
+        modulation,
+        budget.eb_no_db(modulation)
+    ));
+    svg.push_str(&format!("    <text x=\"10\" y=\"296\">BER: {:.2e}</text>\n", budget.ber(modulation)));
+    svg.push_str(&format!(
+        "    <text x=\"10\" y=\"316\">Margin (BER {:e}): {}</text>\n",
+        SUMMARY_TARGET_BER, margin
+    ));
+    svg.push_str("  </g>\n");
+    svg.push_str("</svg>\n");
+
+    svg
+}
+
+pub fn generate_html_summary(
+    budget: &LinkBudget,
+    modulation: Option<&Modulation>,
+    output_path: &str,
+) -> std::io::Result<()> {
+    let html: String = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n</head>\n<body>\n{}</body>\n</html>\n",
+        escape_xml(&budget.name),
+        generate_svg(budget, modulation)
+    );
+
+    fs::write(output_path, html)
+}
+
+pub fn generate_html_comparison(budgets: &[&LinkBudget], output_path: &str) -> std::io::Result<()> {
+    // one column per budget, e.g. uplink against downlink
+    let mut table: String = String::new();
+    table.push_str("<table>\n");
+
+    let mut push_row = |label: &str, cells: Vec<String>| {
+        table.push_str(&format!("  <tr><th>{}</th>", label));
+        for cell in cells {
+            table.push_str(&format!("<td>{}</td>", cell));
+        }
+        table.push_str("</tr>\n");
+    };
+
+    push_row("Link", budgets.iter().map(|b| escape_xml(&b.name)).collect());
+    push_row("Frequency", budgets.iter().map(|b| si(b.path_loss.frequency, "Hz")).collect());
+    push_row("Bandwidth", budgets.iter().map(|b| si(b.bandwidth, "Hz")).collect());
+    push_row("Distance", budgets.iter().map(|b| format_distance(b.path_loss.distance)).collect());
+    push_row("EIRP", budgets.iter().map(|b| format!("{:.2} dBm", b.transmitter.eirp_dbm())).collect());
+    push_row("Path Loss", budgets.iter().map(|b| format!("{:.2} dB", b.path_loss())).collect());
+    push_row("SNR", budgets.iter().map(|b| format!("{:.2} dB", b.snr())).collect());
+    push_row("PHY Rate", budgets.iter().map(|b| format_phy_rate(b)).collect());
+
+    table.push_str("</table>\n");
+
+    let html: String = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Link Budget Comparison</title>\n</head>\n<body>\n{}</body>\n</html>\n",
+        table
+    );
+
+    fs::write(output_path, html)
+}
+
+pub fn write_csv(rows: &[(f64, f64)], headers: (&str, &str), path: &str) -> std::io::Result<()> {
+    // two column CSV, x is written exactly and y in scientific notation (BER spans decades)
+    let mut file = fs::File::create(path)?;
+
+    writeln!(file, "{},{}", headers.0, headers.1)?;
+    for (x, y) in rows {
+        writeln!(file, "{},{:.6e}", x, y)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ber::ber_curve;
+    use crate::budget::LinkBudget;
+    use crate::modulation::Modulation;
+    use crate::receiver::Receiver;
+    use crate::transmitter::Transmitter;
+    use crate::PathLoss;
+
+    fn budget() -> LinkBudget {
+        LinkBudget {
+            name: "Plot Link".to_string(),
+            bandwidth: 36.0e6,
+            transmitter: Transmitter {
+                output_power: 40.0,
+                gain: 35.0,
+                bandwidth: 36.0e6,
+            },
+            receiver: Receiver {
+                gain: 40.0,
+                temperature: 290.0,
+                noise_figure: 3.0,
+                bandwidth: 36.0e6,
+                noise_components: None,
+            },
+            path_loss: PathLoss {
+                frequency: 12.0e9,
+                distance: 1.0e6,
+                excess_loss_db: None,
+            },
+            frequency_dependent_loss: None,
+        }
+    }
+
+    #[test]
+    fn generate_svg() {
+        let svg: String = super::generate_svg(&budget(), None);
+
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains("Plot Link"));
+        assert!(svg.contains("Frequency: 12.0 GHz"));
+        assert!(svg.contains("Bandwidth: 36.0 MHz"));
+        assert!(svg.contains("SNR:"));
+        assert!(svg.contains("Eb/No (QPSK):"));
+        assert!(svg.contains("Margin"));
+    }
+
+    #[test]
+    fn generate_svg_escapes_name() {
+        let mut budget = budget();
+        budget.name = "A&B <x>".to_string();
+
+        let svg: String = super::generate_svg(&budget, None);
+
+        assert!(svg.contains(">A&amp;B &lt;x&gt;</text>"));
+        assert!(!svg.contains("<x>"));
+    }
+
+    #[test]
+    fn generate_svg_modulation() {
+        let budget = budget();
+        let svg: String = super::generate_svg(&budget, Some(&Modulation::Mqam(16)));
+
+        assert!(svg.contains(&format!(
+            "Eb/No (16-QAM): {:.2} dB",
+            budget.eb_no_db(&Modulation::Mqam(16))
+        )));
+        assert!(svg.contains(&format!("Received Power: {:.2} dBm", budget.pin_at_receiver())));
+    }
+
+    #[test]
+    fn generate_html_summary() {
+        let path = crate::utils::temp_path("plot_summary.html");
+        let path: &str = path.to_str().unwrap();
+
+        super::generate_html_summary(&budget(), None, path).unwrap();
+        let html: String = std::fs::read_to_string(path).unwrap();
+
+        assert!(html.contains("<title>Plot Link</title>"));
+        assert!(html.contains("<svg"));
+
+        let mut budget = budget();
+        budget.name = "A&B <x>".to_string();
+        super::generate_html_summary(&budget, None, path).unwrap();
+        let html: String = std::fs::read_to_string(path).unwrap();
+
+        assert!(html.contains("<title>A&amp;B &lt;x&gt;</title>"));
+        assert!(!html.contains("<x>"));
+    }
+
+    #[test]
+    fn generate_html_comparison() {
+        let path = crate::utils::temp_path("plot_comparison.html");
+        let path: &str = path.to_str().unwrap();
+
+        let uplink = budget();
+        let mut downlink = budget();
+        downlink.name = "Downlink".to_string();
+        downlink.path_loss.frequency = 20.0e9;
+
+        super::generate_html_comparison(&[&uplink, &downlink], path).unwrap();
+        let html: String = std::fs::read_to_string(path).unwrap();
+
+        assert!(html.contains("<td>Plot Link</td>"));
+        assert!(html.contains("<td>Downlink</td>"));
+        assert!(html.contains("<td>20.0 GHz</td>"));
+
+        let snr_row: &str = html.lines().find(|line| line.contains("<th>SNR</th>")).unwrap();
+        assert_eq!(2, snr_row.matches("<td>").count());
+        assert!(snr_row.contains(&format!("<td>{:.2} dB</td>", uplink.snr())));
+        assert!(snr_row.contains(&format!("<td>{:.2} dB</td>", downlink.snr())));
+
+        downlink.name = "A&B <x>".to_string();
+        super::generate_html_comparison(&[&uplink, &downlink], path).unwrap();
+        let html: String = std::fs::read_to_string(path).unwrap();
+
+        assert!(html.contains("<td>A&amp;B &lt;x&gt;</td>"));
+        assert!(!html.contains("<x>"));
+    }
+
+    #[test]
+    fn write_csv() {
+        let path = crate::utils::temp_path("ber_qpsk.csv");
+        let path: &str = path.to_str().unwrap();
+
+        let curve = ber_curve(&Modulation::Qpsk, 0.0, 10.0, 1.0);
+        super::write_csv(&curve, ("eb_no_db", "ber"), path).unwrap();
+
+        let csv: String = std::fs::read_to_string(path).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!("eb_no_db,ber", lines[0]);
+        assert_eq!(curve.len() + 1, lines.len());
+        assert!(lines[1].starts_with("0,7.86"));
+    }
+}
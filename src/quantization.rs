@@ -0,0 +1,64 @@
+pub fn quantization_snr_db(bits: u32) -> f64 {
+    // ideal ADC with a full scale sine wave, SNR = 6.02 * N + 1.76 dB
+    6.02 * bits as f64 + 1.76
+}
+
+pub fn enob_from_snr(snr_db: f64) -> f64 {
+    // effective number of bits, inverse of quantization_snr_db
+    (snr_db - 1.76) / 6.02
+}
+
+pub fn processing_gain_db(sample_rate_hz: f64, signal_bandwidth_hz: f64) -> f64 {
+    // oversampling spreads the quantization noise over fs/2, filtering to the signal
+    // bandwidth keeps only BW of it: 10*log10(fs / (2 * BW))
+    10.0 * (sample_rate_hz / (2.0 * signal_bandwidth_hz)).log10()
+}
+
+pub fn effective_snr_with_oversampling(bits: u32, sample_rate_hz: f64, signal_bandwidth_hz: f64) -> f64 {
+    quantization_snr_db(bits) + processing_gain_db(sample_rate_hz, signal_bandwidth_hz)
+}
+
+pub fn sfdr_db_from_bits(bits: u32) -> f64 {
+    // rule of thumb for an ideal converter, dBc with a full scale tone
+    // quantization spurs sit near 6.02 dB per bit below the carrier, real parts are set by their linearity
+    6.02 * bits as f64
+}
+
+#[cfg(test)]
+mod tests {
+
+    #[test]
+    fn twelve_bits() {
+        let snr: f64 = super::quantization_snr_db(12);
+
+        assert_eq!(74.0, snr);
+    }
+
+    #[test]
+    fn enob() {
+        let enob: f64 = super::enob_from_snr(super::quantization_snr_db(10));
+
+        assert_eq!(10.0, enob);
+    }
+
+    #[test]
+    fn four_times_oversampling() {
+        // sampling at 8x the bandwidth is 4x Nyquist, ~6 dB
+        let gain: f64 = super::processing_gain_db(80.0e6, 10.0e6);
+
+        assert!((gain - 6.02).abs() < 0.01);
+    }
+
+    #[test]
+    fn sixteen_bit_oversampled() {
+        // 6.02 * 16 + 1.76 = 98.08 dB, plus 10*log10(100e6 / (2 * 5e6)) = 10 dB
+        let snr: f64 = super::effective_snr_with_oversampling(16, 100.0e6, 5.0e6);
+
+        assert!((snr - 108.08).abs() < 1e-9);
+    }
+
+    #[test]
+    fn sfdr() {
+        assert!((super::sfdr_db_from_bits(12) - 72.24).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,296 @@
+// Rain attenuation on an Earth-space path
+// Specific attenuation from ITU-R P.838-3, path attenuation and the time-percentage
+// scaling from ITU-R P.618, for a ground station at sea level and circular polarization
+
+use crate::conversions::angle::degrees_to_radians;
+
+// the path geometry below assumes elevations of at least 5 degrees
+const MINIMUM_ELEVATION_DEGREES: f64 = 5.0;
+
+// P.618 scaling is only defined for exceedance percentages in [0.001, 5]
+const MINIMUM_PERCENTAGE: f64 = 0.001;
+const MAXIMUM_PERCENTAGE: f64 = 5.0;
+
+// P.838-3 regression coefficients, (a, b, c) per term followed by (m, c) of the linear term
+const K_H: ([(f64, f64, f64); 4], f64, f64) = (
+    [
+        (-5.33980, -0.10008, 1.13098),
+        (-0.35351, 1.26970, 0.45400),
+        (-0.23789, 0.86036, 0.15354),
+        (-0.94158, 0.64552, 0.16817),
+    ],
+    -0.18961,
+    0.71147,
+);
+const K_V: ([(f64, f64, f64); 4], f64, f64) = (
+    [
+        (-3.80595, 0.56934, 0.81061),
+        (-3.44965, -0.22911, 0.51059),
+        (-0.39902, 0.73042, 0.11899),
+        (0.50167, 1.07319, 0.27195),
+    ],
+    -0.16398,
+    0.63297,
+);
+const ALPHA_H: ([(f64, f64, f64); 5], f64, f64) = (
+    [
+        (-0.14318, 1.82442, -0.55187),
+        (0.29591, 0.77564, 0.19822),
+        (0.32177, 0.63773, 0.13164),
+        (-5.37610, -0.96230, 1.47828),
+        (16.1721, -3.29980, 3.43990),
+    ],
+    0.67849,
+    -1.95537,
+);
+const ALPHA_V: ([(f64, f64, f64); 5], f64, f64) = (
+    [
+        (-0.07771, 2.33840, -0.76284),
+        (0.56727, 0.95545, 0.54039),
+        (-0.20238, 1.14520, 0.26809),
+        (-48.2991, 0.791669, 0.116226),
+        (48.5833, 0.791459, 0.116479),
+    ],
+    -0.053739,
+    0.83433,
+);
+
+fn regression(terms: &[(f64, f64, f64)], slope: f64, offset: f64, frequency_ghz: f64) -> f64 {
+    let log_f: f64 = frequency_ghz.log10();
+
+    terms
+        .iter()
+        .map(|(a, b, c)| a * (-((log_f - b) / c).powi(2)).exp())
+        .sum::<f64>()
+        + slope * log_f
+        + offset
+}
+
+pub fn specific_attenuation_coefficients(frequency_ghz: f64) -> (f64, f64) {
+    // (k, alpha) for circular polarization, gamma_R = k * R^alpha in dB/km
+    let k_h: f64 = 10.0_f64.powf(regression(&K_H.0, K_H.1, K_H.2, frequency_ghz));
+    let k_v: f64 = 10.0_f64.powf(regression(&K_V.0, K_V.1, K_V.2, frequency_ghz));
+    let alpha_h: f64 = regression(&ALPHA_H.0, ALPHA_H.1, ALPHA_H.2, frequency_ghz);
+    let alpha_v: f64 = regression(&ALPHA_V.0, ALPHA_V.1, ALPHA_V.2, frequency_ghz);
+
+    let k: f64 = (k_h + k_v) / 2.0;
+    let alpha: f64 = (k_h * alpha_h + k_v * alpha_v) / (2.0 * k);
+
+    (k, alpha)
+}
+
+pub fn specific_attenuation_db_per_km(frequency_ghz: f64, rain_rate_mm_hr: f64) -> f64 {
+    let (k, alpha) = specific_attenuation_coefficients(frequency_ghz);
+
+    k * rain_rate_mm_hr.powf(alpha)
+}
+
+pub fn rain_height_km(latitude_deg: f64) -> f64 {
+    // mean rain height, 5 km in the tropics and lower toward the poles (ITU-R P.839-2)
+    let latitude: f64 = latitude_deg.abs();
+
+    if latitude <= 23.0 {
+        5.0
+    } else {
+        (5.0 - 0.075 * (latitude - 23.0)).max(0.0)
+    }
+}
+
+pub fn attenuation_exceeded_001_db(
+    frequency_ghz: f64,
+    elevation_deg: f64,
+    latitude_deg: f64,
+    rain_rate_001_mm_hr: f64,
+) -> f64 {
+    // A0.01, the attenuation exceeded for 0.01% of an average year
+    let elevation: f64 = elevation_deg.clamp(MINIMUM_ELEVATION_DEGREES, 90.0);
+    let theta: f64 = degrees_to_radians(elevation);
+    let rain_height: f64 = rain_height_km(latitude_deg);
+
+    if rain_height <= 0.0 || rain_rate_001_mm_hr <= 0.0 {
+        return 0.0;
+    }
+
+    let slant_length: f64 = rain_height / theta.sin();
+    let horizontal_length: f64 = slant_length * theta.cos();
+    let gamma: f64 = specific_attenuation_db_per_km(frequency_ghz, rain_rate_001_mm_hr);
+
+    // horizontal reduction factor
+    let r: f64 = 1.0
+        / (1.0 + 0.78 * (horizontal_length * gamma / frequency_ghz).sqrt()
+            - 0.38 * (1.0 - (-2.0 * horizontal_length).exp()));
+
+    let zeta: f64 = (rain_height / (horizontal_length * r)).atan().to_degrees();
+    let rain_length: f64 = if zeta > elevation {
+        horizontal_length * r / theta.cos()
+    } else {
+        slant_length
+    };
+
+    // vertical adjustment factor
+    let chi: f64 = if latitude_deg.abs() < 36.0 {
+        36.0 - latitude_deg.abs()
+    } else {
+        0.0
+    };
+    let v: f64 = 1.0
+        / (1.0
+            + theta.sin().sqrt()
+                * (31.0 * (1.0 - (-(elevation / (1.0 + chi))).exp()) * (rain_length * gamma).sqrt()
+                    / (frequency_ghz * frequency_ghz)
+                    - 0.45));
+
+    gamma * rain_length * v
+}
+
+pub fn attenuation_for_availability(
+    frequency_ghz: f64,
+    elevation_deg: f64,
+    latitude_deg: f64,
+    rain_rate_001_mm_hr: f64,
+    availability_percent: f64,
+) -> f64 {
+    // fade depth (dB) to budget for the given availability, e.g. 99.9% of the year
+    // The outage percentage p = 100 - availability is clamped to P.618's [0.001, 5] range
+    let a_001: f64 = attenuation_exceeded_001_db(frequency_ghz, elevation_deg, latitude_deg, rain_rate_001_mm_hr);
+    if a_001 <= 0.0 {
+        return 0.0;
+    }
+
+    let p: f64 = (100.0 - availability_percent).clamp(MINIMUM_PERCENTAGE, MAXIMUM_PERCENTAGE);
+    let elevation: f64 = elevation_deg.clamp(MINIMUM_ELEVATION_DEGREES, 90.0);
+    let sin_theta: f64 = degrees_to_radians(elevation).sin();
+    let latitude: f64 = latitude_deg.abs();
+
+    let beta: f64 = if p >= 1.0 || latitude >= 36.0 {
+        0.0
+    } else if elevation >= 25.0 {
+        -0.005 * (latitude - 36.0)
+    } else {
+        -0.005 * (latitude - 36.0) + 1.8 - 4.25 * sin_theta
+    };
+
+    let exponent: f64 = 0.655 + 0.033 * p.ln() - 0.045 * a_001.ln() - beta * (1.0 - p) * sin_theta;
+
+    a_001 * (p / 0.01).powf(-exponent)
+}
+
+pub fn xpd_from_attenuation_db(
+    copol_attenuation_db: f64,
+    frequency_ghz: f64,
+    elevation_deg: f64,
+    polarization_tilt_deg: f64,
+) -> f64 {
+    // rain cross-polarization discrimination, ITU-R P.618 section 4.1, valid 6-55 GHz
+    // and elevations up to 60 degrees (both are clamped). XPD falls as the co-polar
+    // attenuation grows: heavier rain means less isolation between the two polarizations.
+    // Tilt is relative to horizontal, 45 degrees for circular polarization.
+    // The canting angle spread and ice term use their 0.01% of time values.
+    if copol_attenuation_db <= 0.0 {
+        // no rain on the path, the antenna's own isolation is the limit
+        return f64::INFINITY;
+    }
+
+    let f: f64 = frequency_ghz.clamp(6.0, 55.0);
+    let elevation: f64 = elevation_deg.min(60.0);
+
+    let c_f: f64 = if f < 9.0 {
+        60.0 * f.log10() - 28.3
+    } else if f < 36.0 {
+        26.0 * f.log10() + 4.1
+    } else {
+        35.9 * f.log10() - 11.3
+    };
+
+    let v: f64 = if f < 9.0 {
+        12.8 * f.powf(0.19)
+    } else if f < 36.0 {
+        22.6
+    } else {
+        13.0 * f.powf(0.15)
+    };
+
+    let c_a: f64 = v * copol_attenuation_db.log10();
+    let c_tau: f64 = -10.0 * (1.0 - 0.484 * (1.0 + degrees_to_radians(4.0 * polarization_tilt_deg).cos())).log10();
+    let c_theta: f64 = -40.0 * degrees_to_radians(elevation).cos().log10();
+
+    // canting angle spread of 10 degrees
+    let c_sigma: f64 = 0.0053 * 10.0 * 10.0;
+
+    let xpd_rain: f64 = c_f - c_a + c_tau + c_theta + c_sigma;
+
+    // ice crystals, (0.3 + 0.1 * log10(p)) / 2 of the rain XPD at p = 0.01
+    let c_ice: f64 = xpd_rain * (0.3 + 0.1 * 0.01_f64.log10()) / 2.0;
+
+    xpd_rain - c_ice
+}
+
+#[cfg(test)]
+mod tests {
+
+    #[test]
+    fn specific_attenuation_coefficients() {
+        // P.838-3 tabulates 0.02386/1.1825 (H) and 0.02455/1.1216 (V) at 12 GHz,
+        // which combine to 0.0242/1.152 for circular polarization
+        let (k, alpha) = super::specific_attenuation_coefficients(12.0);
+
+        assert!((k - 0.0242).abs() < 1e-4);
+        assert!((alpha - 1.152).abs() < 1e-3);
+    }
+
+    #[test]
+    fn availability_at_one_hundredth_percent() {
+        let a_001: f64 = super::attenuation_exceeded_001_db(20.0, 30.0, 40.0, 42.0);
+        let a: f64 = super::attenuation_for_availability(20.0, 30.0, 40.0, 42.0, 99.99);
+
+        assert!((a - a_001).abs() < 1e-9);
+    }
+
+    #[test]
+    fn deeper_availability_needs_more_margin() {
+        let availabilities: [f64; 5] = [99.0, 99.5, 99.9, 99.99, 99.999];
+
+        let mut previous: f64 = 0.0;
+        for availability in availabilities {
+            let attenuation: f64 = super::attenuation_for_availability(20.0, 30.0, 40.0, 42.0, availability);
+
+            assert!(attenuation > previous);
+            previous = attenuation;
+        }
+    }
+
+    #[test]
+    fn ka_band_fades_more_than_ku() {
+        let ku: f64 = super::attenuation_for_availability(12.0, 30.0, 40.0, 42.0, 99.9);
+        let ka: f64 = super::attenuation_for_availability(20.0, 30.0, 40.0, 42.0, 99.9);
+
+        assert!(ka > ku);
+        // a few dB at Ku-band for 99.9%
+        assert!(ku > 0.5 && ku < 5.0);
+    }
+
+    #[test]
+    fn xpd_clear_sky() {
+        assert_eq!(f64::INFINITY, super::xpd_from_attenuation_db(0.0, 20.0, 30.0, 45.0));
+
+        // a light fade still isolates well
+        assert!(super::xpd_from_attenuation_db(0.1, 20.0, 30.0, 45.0) > 40.0);
+    }
+
+    #[test]
+    fn xpd_collapses_in_heavy_rain() {
+        let light: f64 = super::xpd_from_attenuation_db(1.0, 20.0, 30.0, 45.0);
+        let heavy: f64 = super::xpd_from_attenuation_db(15.0, 20.0, 30.0, 45.0);
+
+        assert!(heavy < light);
+        assert!(heavy < 20.0);
+    }
+
+    #[test]
+    fn linear_polarization_isolates_better_than_circular() {
+        let horizontal: f64 = super::xpd_from_attenuation_db(5.0, 20.0, 30.0, 0.0);
+        let circular: f64 = super::xpd_from_attenuation_db(5.0, 20.0, 30.0, 45.0);
+
+        assert!(horizontal > circular);
+    }
+}
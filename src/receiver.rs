@@ -1,12 +1,97 @@
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone)]
 pub struct Receiver {
     pub gain: f64,         // dB
     pub temperature: f64,  // K
     pub noise_figure: f64, // dB
     pub bandwidth: f64,    // Hz
+    // front end breakdown for G/T, None falls back to temperature and noise_figure
+    pub noise_components: Option<NoiseComponents>,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NoiseComponents {
+    pub antenna_temperature: f64,  // K, spillover and ohmic contributions of the antenna itself
+    pub sky_temperature: f64,      // K, brightness temperature the beam sees (cold sky or warm ground)
+    pub feed_loss: f64,            // dB, between the antenna port and the LNA
+    pub lna_noise_figure: f64,     // dB
+    pub physical_temperature: f64, // K, of the feed
+}
+
+impl NoiseComponents {
+    pub fn system_noise_temperature(&self) -> f64 {
+        system_noise_temperature_k(
+            self.antenna_temperature,
+            self.sky_temperature,
+            self.feed_loss,
+            self.lna_noise_figure,
+            self.physical_temperature,
+        )
+    }
+}
+
+pub fn system_noise_temperature_k(
+    antenna_temp_k: f64,
+    sky_temp_k: f64,
+    feed_loss_db: f64,
+    lna_noise_figure_db: f64,
+    physical_temp_k: f64,
+) -> f64 {
+    // referenced to the antenna port:
+    // Tsys = Tsky + Tant + (L - 1) * Tp + L * Tlna
+    // a lossy feed adds its own thermal noise and divides down everything after it
+    let feed_loss: f64 = 10.0_f64.powf(feed_loss_db / 10.0);
+    let feed_temperature: f64 = crate::conversions::noise::passive_device_noise_temperature_k(feed_loss_db, physical_temp_k);
+    let lna_temperature: f64 = crate::conversions::noise::noise_temperature_from_noise_figure(lna_noise_figure_db);
+
+    sky_temp_k + antenna_temp_k + feed_temperature + feed_loss * lna_temperature
 }
 
 impl Receiver {
+    pub fn from_noise_figure(gain: f64, noise_figure_db: f64, bandwidth: f64) -> Receiver {
+        // input at the T0 = 290 K reference, so the noise floor (kT0B plus the noise figure,
+        // see calculate_noise_power) equals k * (T0 + Te) * B
+        Receiver {
+            gain,
+            temperature: crate::constants::REFERENCE_TEMPERATURE,
+            noise_figure: noise_figure_db,
+            bandwidth,
+            noise_components: None,
+        }
+    }
+
+    pub fn from_noise_temperature(gain: f64, noise_temperature_k: f64, bandwidth: f64) -> Receiver {
+        // noise_temperature_k is the receiver's equivalent noise temperature Te, converted
+        // to the noise figure the noise floor uses, e.g. 288.6 K -> 3 dB
+        let noise_figure: f64 = crate::conversions::noise::noise_figure_from_noise_temperature(noise_temperature_k);
+
+        Receiver::from_noise_figure(gain, noise_figure, bandwidth)
+    }
+
+    pub fn noise_temperature(&self) -> f64 {
+        // equivalent noise temperature Te of the receiver (K), from its noise figure
+        crate::conversions::noise::noise_temperature_from_noise_figure(self.noise_figure)
+    }
+
+    pub fn system_noise_temperature(&self) -> f64 {
+        // K, from the front end components when given, otherwise the input temperature
+        // plus the receiver's equivalent noise temperature
+        match &self.noise_components {
+            Some(components) => components.system_noise_temperature(),
+            None => {
+                self.temperature + crate::conversions::noise::noise_temperature_from_noise_figure(self.noise_figure)
+            }
+        }
+    }
+
+    pub fn g_over_t_db(&self) -> f64 {
+        // figure of merit, dB/K
+        self.gain - 10.0 * self.system_noise_temperature().log10()
+    }
+
     pub fn calculate_noise_floor(&self) -> f64 {
+        // kTB at the input temperature, calculate_noise_power adds the noise figure
         let receiver_noise_floor_power =
             crate::conversions::noise::noise_power_from_bandwidth(self.temperature, self.bandwidth);
 
@@ -22,15 +107,30 @@ impl Receiver {
 
         let receiver_total_noise_power = receiver_noise_floor_dbm + self.noise_figure;
 
+        // input_power and the noise are both referenced to the receiver input (before gain)
         // Assumes receiver input power is spread across the bandwidth
         // returns value in dB
         input_power - receiver_total_noise_power
     }
+
+    pub fn calculate_snr_at_input(&self, signal_at_antenna_dbm: f64) -> f64 {
+        // the receiver gain lifts the signal and its input noise equally, so it cancels
+        let signal_at_output_dbm: f64 = signal_at_antenna_dbm + self.gain;
+        let noise_at_output_dbm: f64 = self.calculate_noise_power() + self.gain;
+
+        signal_at_output_dbm - noise_at_output_dbm
+    }
+
+    pub fn calculate_snr_at_output(&self, signal_after_gain_dbm: f64) -> f64 {
+        // signal already includes the receiver gain (e.g. measured after the LNA),
+        // so the noise is referenced to the same plane
+        signal_after_gain_dbm - (self.calculate_noise_power() + self.gain)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::receiver::Receiver;
+    use crate::receiver::{NoiseComponents, Receiver};
 
     #[test]
     fn calculate_noise_floor() {
@@ -39,11 +139,12 @@ mod tests {
             temperature: 290.0,
             noise_figure: 3.0, // not used
             bandwidth: 100.0e6,
+            noise_components: None,
         };
 
         let noise_floor: f64 = receiver.calculate_noise_floor();
 
-        assert_eq!(-93.97722915699808, noise_floor);
+        assert_eq!(-93.97518719422811, noise_floor);
     }
 
     #[test]
@@ -53,12 +154,13 @@ mod tests {
             temperature: 290.0,
             noise_figure: 3.0,
             bandwidth: 100.0e6,
+            noise_components: None,
         };
 
         let noise_power: f64 = receiver.calculate_noise_power();
 
         // noise floor + noise figure
-        assert_eq!(-90.97722915699808, noise_power);
+        assert_eq!(-90.97518719422811, noise_power);
     }
 
     #[test]
@@ -68,6 +170,7 @@ mod tests {
             temperature: 290.0,
             noise_figure: 3.0,
             bandwidth: 100.0e6,
+            noise_components: None,
         };
 
         let input_power: f64 = -70.0; // dBm
@@ -76,6 +179,106 @@ mod tests {
         // returns value in dB
         let snr: f64 = receiver.calculate_snr(input_power);
 
-        assert_eq!(20.977229156998078, snr);
+        assert_eq!(20.975187194228113, snr);
+    }
+
+    #[test]
+    fn snr_is_invariant_to_receiver_gain() {
+        let mut receiver = Receiver {
+            gain: 10.0,
+            temperature: 290.0,
+            noise_figure: 3.0,
+            bandwidth: 100.0e6,
+            noise_components: None,
+        };
+        let low_gain: f64 = receiver.calculate_snr_at_input(-70.0);
+
+        receiver.gain = 40.0;
+        let high_gain: f64 = receiver.calculate_snr_at_input(-70.0);
+
+        assert_eq!(low_gain, high_gain);
+        assert_eq!(receiver.calculate_snr(-70.0), high_gain);
+    }
+
+    #[test]
+    fn snr_at_output() {
+        let receiver = Receiver {
+            gain: 30.0,
+            temperature: 290.0,
+            noise_figure: 3.0,
+            bandwidth: 100.0e6,
+            noise_components: None,
+        };
+
+        // -70 dBm at the antenna is -40 dBm after 30 dB of gain, the same SNR at either plane
+        let snr: f64 = receiver.calculate_snr_at_output(-40.0);
+
+        assert!((snr - receiver.calculate_snr_at_input(-70.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn from_noise_figure() {
+        let receiver = Receiver::from_noise_figure(30.0, 3.0, 10.0e6);
+
+        assert_eq!(290.0, receiver.temperature);
+        assert!((receiver.noise_temperature() - 288.6).abs() < 0.1);
+        assert!((receiver.system_noise_temperature() - 578.6).abs() < 0.1);
+    }
+
+    #[test]
+    fn from_noise_temperature() {
+        let receiver = Receiver::from_noise_temperature(30.0, 288.6, 10.0e6);
+
+        assert!((receiver.noise_figure - 3.0).abs() < 0.01);
+        assert!((receiver.noise_temperature() - 288.6).abs() < 1e-9);
+
+        // same noise floor either way
+        let from_figure = Receiver::from_noise_figure(30.0, receiver.noise_figure, 10.0e6);
+        assert_eq!(from_figure.calculate_noise_power(), receiver.calculate_noise_power());
+    }
+
+    fn ka_band_components(sky_temperature: f64) -> NoiseComponents {
+        NoiseComponents {
+            antenna_temperature: 20.0,
+            sky_temperature,
+            feed_loss: 0.3,
+            lna_noise_figure: 0.5,
+            physical_temperature: 290.0,
+        }
+    }
+
+    #[test]
+    fn system_noise_temperature_k() {
+        // lossless feed: just the sky, antenna, and LNA temperatures
+        let temperature: f64 = crate::receiver::system_noise_temperature_k(20.0, 10.0, 0.0, 0.0, 290.0);
+
+        assert_eq!(30.0, temperature);
+    }
+
+    #[test]
+    fn sky_versus_ground() {
+        let cold: f64 = ka_band_components(10.0).system_noise_temperature();
+        let warm: f64 = ka_band_components(290.0).system_noise_temperature();
+
+        // ~89 K at cold sky, ~369 K looking at the ground
+        assert!(cold > 80.0 && cold < 100.0);
+        assert!(warm > 3.0 * cold);
+    }
+
+    #[test]
+    fn g_over_t_db() {
+        let mut receiver = Receiver {
+            gain: 40.0,
+            temperature: 290.0,
+            noise_figure: 3.0,
+            bandwidth: 100.0e6,
+            noise_components: None,
+        };
+        let room_temperature: f64 = receiver.g_over_t_db();
+
+        receiver.noise_components = Some(ka_band_components(10.0));
+        let cold_sky: f64 = receiver.g_over_t_db();
+
+        assert!(cold_sky > room_temperature + 5.0);
     }
 }
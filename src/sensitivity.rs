@@ -0,0 +1,100 @@
+// Receiver sensitivity
+// Thermal noise at 290 K is -174 dBm/Hz (constants::KT0_DBM_PER_HZ)
+
+use crate::constants::KT0_DBM_PER_HZ;
+
+pub fn noise_floor_dbm(bandwidth_hz: f64, noise_figure_db: f64) -> f64 {
+    // the T0 = 290 K case of noise_floor_dbm_at_temp
+    KT0_DBM_PER_HZ + 10.0 * bandwidth_hz.log10() + noise_figure_db
+}
+
+pub fn noise_floor_dbm_at_temp(bandwidth_hz: f64, noise_figure_db: f64, temp_k: f64) -> f64 {
+    // kTB at a reference temperature other than 290 K, e.g. a cold environment
+    let thermal_noise_w: f64 = crate::conversions::noise::noise_power_from_bandwidth(temp_k, bandwidth_hz);
+
+    crate::conversions::power::watts_to_dbm(thermal_noise_w) + noise_figure_db
+}
+
+pub fn sensitivity_dbm(bandwidth_hz: f64, noise_figure_db: f64, required_snr_db: f64) -> f64 {
+    // minimum input power for the required SNR in the given bandwidth
+    noise_floor_dbm(bandwidth_hz, noise_figure_db) + required_snr_db
+}
+
+pub fn sensitivity_matched_filter_dbm(
+    bit_rate_bps: f64,
+    noise_figure_db: f64,
+    required_eb_no_db: f64,
+    implementation_loss_db: f64,
+) -> f64 {
+    // with a matched filter, S = Eb/No + 10*log10(Rb) + kT + NF + losses
+    KT0_DBM_PER_HZ + 10.0 * bit_rate_bps.log10() + noise_figure_db + required_eb_no_db + implementation_loss_db
+}
+
+pub fn max_range_m(eirp_dbm: f64, rx_gain_db: f64, frequency_hz: f64, sensitivity_dbm: f64) -> f64 {
+    // distance where the received power, EIRP - FSPL + rx gain, falls to the sensitivity
+    // FSPL = 20*log10(4 * pi * d / lambda) inverted for d, free space only
+    let allowed_path_loss_db: f64 = eirp_dbm + rx_gain_db - sensitivity_dbm;
+    let wavelength: f64 = crate::conversions::frequency::frequency_to_wavelength(frequency_hz);
+
+    wavelength / (4.0 * std::f64::consts::PI) * 10.0_f64.powf(allowed_path_loss_db / 20.0)
+}
+
+#[cfg(test)]
+mod tests {
+
+    #[test]
+    fn noise_floor_dbm() {
+        let noise_floor: f64 = super::noise_floor_dbm(1.0e6, 3.0);
+
+        assert_eq!(-110.97518719422808, noise_floor);
+    }
+
+    #[test]
+    fn noise_floor_dbm_at_290_k() {
+        // kTB from Boltzmann's constant and the -174 dBm/Hz density share one base
+        let computed: f64 = super::noise_floor_dbm_at_temp(1.0e6, 3.0, 290.0);
+
+        assert!((computed - super::noise_floor_dbm(1.0e6, 3.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn noise_floor_dbm_at_200_k() {
+        // 10*log10(200 / 290) = -1.61 dB below the 290 K floor
+        let cold: f64 = super::noise_floor_dbm_at_temp(1.0e6, 3.0, 200.0);
+        let standard: f64 = super::noise_floor_dbm_at_temp(1.0e6, 3.0, 290.0);
+
+        assert!((cold - standard + 1.6137).abs() < 1e-4);
+        assert!((cold - super::noise_floor_dbm(1.0e6, 3.0) + 1.6137).abs() < 1e-4);
+    }
+
+    #[test]
+    fn sensitivity_dbm() {
+        let sensitivity: f64 = super::sensitivity_dbm(1.0e6, 3.0, 10.0);
+
+        assert_eq!(-100.97518719422808, sensitivity);
+    }
+
+    #[test]
+    fn sensitivity_matched_filter_dbm() {
+        // 1 Mbps, 3 dB NF, BPSK at 1e-5 (~9.6 dB), 2 dB implementation loss
+        let sensitivity: f64 = super::sensitivity_matched_filter_dbm(1.0e6, 3.0, 9.6, 2.0);
+
+        assert_eq!(-99.37518719422809, sensitivity);
+    }
+
+    #[test]
+    fn max_range_m() {
+        // 2.4 GHz, 20 dBm EIRP, 3 dBi receive antenna, 1 MHz at 10 dB SNR
+        let sensitivity: f64 = super::sensitivity_dbm(1.0e6, 5.0, 10.0);
+        let range: f64 = super::max_range_m(20.0, 3.0, 2.4e9, sensitivity);
+
+        let path_loss = crate::PathLoss {
+            frequency: 2.4e9,
+            distance: range,
+            excess_loss_db: None,
+        };
+        let received: f64 = 20.0 - path_loss.calculate() + 3.0;
+
+        assert!((received - sensitivity).abs() < 1e-9);
+    }
+}
@@ -1,5 +1,46 @@
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone)]
 pub struct Transmitter {
     pub output_power: f64, // dBm
     pub gain: f64,         // dB
     pub bandwidth: f64,    // Hz
 }
+
+impl Transmitter {
+    /// Effective isotropic radiated power in dBm (output_power in dBm plus antenna gain in dBi)
+    pub fn eirp_dbm(&self) -> f64 {
+        self.output_power + self.gain
+    }
+
+    /// Effective isotropic radiated power in dBW
+    pub fn eirp_dbw(&self) -> f64 {
+        self.eirp_dbm() - 30.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Transmitter;
+
+    #[test]
+    fn eirp_dbm() {
+        let transmitter = Transmitter {
+            output_power: 10.0,
+            gain: 35.0,
+            bandwidth: 100.0e6, // not used
+        };
+
+        assert_eq!(45.0, transmitter.eirp_dbm());
+    }
+
+    #[test]
+    fn eirp_dbw() {
+        let transmitter = Transmitter {
+            output_power: 10.0,
+            gain: 35.0,
+            bandwidth: 100.0e6, // not used
+        };
+
+        assert_eq!(15.0, transmitter.eirp_dbw());
+    }
+}
@@ -0,0 +1,70 @@
+// prefixes for scaling up, values below 1 keep the base unit
+const SI_PREFIXES: [&str; 6] = ["", "k", "M", "G", "T", "P"];
+
+pub fn si(value: f64, unit: &str) -> String {
+    // one decimal with the largest prefix that keeps the magnitude at or above 1,
+    // e.g. si(36.0e6, "Hz") is "36.0 MHz" and si(54.0e6, "bps") is "54.0 Mbps"
+    let mut scaled: f64 = value;
+    let mut index: usize = 0;
+
+    while scaled.abs() >= 1000.0 && index < SI_PREFIXES.len() - 1 {
+        scaled /= 1000.0;
+        index += 1;
+    }
+
+    format!("{:.1} {}{}", scaled, SI_PREFIXES[index], unit)
+}
+
+pub fn escape_xml(text: &str) -> String {
+    // for user strings (e.g. budget names) in SVG and HTML text or attribute values
+    let mut escaped: String = String::with_capacity(text.len());
+
+    for character in text.chars() {
+        match character {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(character),
+        }
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+
+    #[test]
+    fn escape_xml() {
+        assert_eq!("A&amp;B &lt;x&gt;", super::escape_xml("A&B <x>"));
+        assert_eq!("&quot;AT&amp;T&quot;", super::escape_xml("\"AT&T\""));
+        assert_eq!("Ka-band LEO", super::escape_xml("Ka-band LEO"));
+    }
+
+    #[test]
+    fn bandwidth() {
+        assert_eq!("36.0 MHz", super::si(36.0e6, "Hz"));
+    }
+
+    #[test]
+    fn bit_rate() {
+        assert_eq!("54.0 Mbps", super::si(54.0e6, "bps"));
+    }
+
+    #[test]
+    fn no_prefix() {
+        assert_eq!("999.0 Hz", super::si(999.0, "Hz"));
+        assert_eq!("0.5 Hz", super::si(0.5, "Hz"));
+    }
+
+    #[test]
+    fn prefix_boundaries() {
+        assert_eq!("1.0 kHz", super::si(1.0e3, "Hz"));
+        assert_eq!("12.0 GHz", super::si(12.0e9, "Hz"));
+        assert_eq!("-2.5 kHz", super::si(-2.5e3, "Hz"));
+
+        // past peta the value keeps growing rather than running out of prefixes
+        assert_eq!("2000.0 PHz", super::si(2.0e18, "Hz"));
+    }
+}
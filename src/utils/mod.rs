@@ -0,0 +1,8 @@
+pub mod format;
+
+#[cfg(test)]
+pub(crate) fn temp_path(name: &str) -> std::path::PathBuf {
+    // test outputs go in the temp dir, prefixed with the process id so concurrent
+    // test runs don't write over each other
+    std::env::temp_dir().join(format!("linkbudget_{}_{}", std::process::id(), name))
+}